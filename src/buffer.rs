@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use stumpless_sys::*;
+
+use std::ffi::CString;
+use std::marker::PhantomData;
+use std::os::raw::c_char;
+
+use crate::error::{last_error, StumplessError};
+use crate::filter;
+use crate::Target;
+
+/// A target that logs into a ring buffer in memory.
+///
+/// Each message is written into the buffer without its trailing newline and
+/// followed by a NULL character. Once the buffer is full, new messages
+/// overwrite the oldest ones, so the buffer needs to be read often enough to
+/// keep up with the messages being written to it.
+pub struct BufferTarget<'a> {
+    target: *mut stumpless_target,
+    capacity: usize,
+    buffer: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> BufferTarget<'a> {
+    /// Opens a target that writes into the given buffer, which must stay
+    /// borrowed for as long as the target is open.
+    pub fn new(name: &str, buffer: &'a mut [u8]) -> Result<Self, StumplessError> {
+        let c_name = CString::new(name)?;
+        let buffer_target = unsafe {
+            stumpless_open_buffer_target(
+                c_name.as_ptr(),
+                buffer.as_mut_ptr() as *mut c_char,
+                buffer.len(),
+            )
+        };
+
+        if buffer_target.is_null() {
+            Err(last_error())
+        } else {
+            Ok(BufferTarget {
+                target: buffer_target,
+                capacity: buffer.len(),
+                buffer: PhantomData,
+            })
+        }
+    }
+
+    /// Reads the next message out of the buffer, or `None` if all of the
+    /// messages have been read.
+    ///
+    /// If older messages were overwritten before they were read, then the
+    /// first message read afterwards may only be the end of a message.
+    pub fn read(&self) -> Result<Option<String>, StumplessError> {
+        let mut message = vec![0u8; self.capacity + 1];
+        let read_result = unsafe {
+            stumpless_read_buffer(
+                self.target,
+                message.as_mut_ptr() as *mut c_char,
+                message.len(),
+            )
+        };
+
+        match read_result {
+            0 => Err(last_error()),
+            1 => Ok(None),
+            length => {
+                message.truncate(length - 1);
+                Ok(Some(String::from_utf8_lossy(&message).into_owned()))
+            }
+        }
+    }
+
+    /// Reads all of the messages that have not been read yet, oldest first.
+    pub fn read_all(&self) -> Result<Vec<String>, StumplessError> {
+        let mut messages = Vec::new();
+        while let Some(message) = self.read()? {
+            messages.push(message);
+        }
+
+        Ok(messages)
+    }
+}
+
+unsafe impl Send for BufferTarget<'_> {}
+unsafe impl Sync for BufferTarget<'_> {}
+
+impl Target for BufferTarget<'_> {
+    fn get_pointer(&self) -> *mut stumpless_target {
+        self.target
+    }
+}
+
+impl Drop for BufferTarget<'_> {
+    fn drop(&mut self) {
+        filter::remove_filter(self.target);
+        unsafe {
+            stumpless_close_buffer_target(self.target);
+        }
+    }
+}
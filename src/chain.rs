@@ -0,0 +1,189 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use stumpless_sys::stumpless_target;
+
+use std::cell::RefCell;
+use std::sync::{Arc, RwLock};
+
+use crate::entry::Entry;
+use crate::error::StumplessError;
+use crate::function::FunctionTarget;
+use crate::severity::Severity;
+use crate::Target;
+
+type ChainMembers = RwLock<Vec<Box<dyn Target + Send>>>;
+
+thread_local! {
+    // the results of the last entry passed down a chain on this thread, which
+    // are picked up by the chain call that logged it
+    static CHAIN_RESULTS: RefCell<Option<Vec<Result<u32, StumplessError>>>> =
+        const { RefCell::new(None) };
+}
+
+/// The result of logging an entry to each target in a [`ChainTarget`].
+#[derive(Debug)]
+pub struct ChainReport {
+    results: Vec<Result<u32, StumplessError>>,
+}
+
+impl ChainReport {
+    /// The result from each target in the chain, in the order that they were
+    /// added. This is empty if the chain itself did not log the entry, for
+    /// example because of its mask.
+    pub fn results(&self) -> &[Result<u32, StumplessError>] {
+        &self.results
+    }
+
+    /// The errors from the targets that failed, along with their position in
+    /// the chain.
+    pub fn errors(&self) -> impl Iterator<Item = (usize, &StumplessError)> {
+        self.results
+            .iter()
+            .enumerate()
+            .filter_map(|(i, result)| result.as_ref().err().map(|err| (i, err)))
+    }
+
+    /// Whether every target in the chain logged the entry.
+    pub fn is_ok(&self) -> bool {
+        self.results.iter().all(Result::is_ok)
+    }
+
+    // the combined result, which is the first error if there was one
+    fn into_result(self) -> Result<u32, StumplessError> {
+        self.results.into_iter().sum()
+    }
+}
+
+/// A target that logs each entry to every target in a list.
+///
+/// The chain has its own mask, filter, and defaults, which are applied before
+/// the entry is passed on. Each target in the chain then applies its own mask
+/// and filter as usual. The chain owns its targets, which are closed along
+/// with it.
+///
+/// An entry is passed to every target even if some of them fail. The `Target`
+/// methods return the first of these errors, and
+/// [`ChainTarget::add_entry_report`] gives the result from each target
+/// instead.
+///
+/// ```no_run
+/// # use stumpless::{ChainTarget, FileTarget, StreamTarget, Target};
+/// let chain = ChainTarget::new(
+///     "app",
+///     vec![
+///         Box::new(FileTarget::new("app.log").unwrap()),
+///         Box::new(StreamTarget::stderr("app-stderr").unwrap()),
+///     ],
+/// )
+/// .unwrap();
+/// chain.info("logged to app.log and stderr").unwrap();
+/// ```
+pub struct ChainTarget {
+    target: FunctionTarget,
+    targets: Arc<ChainMembers>,
+}
+
+impl ChainTarget {
+    pub fn new(name: &str, targets: Vec<Box<dyn Target + Send>>) -> Result<Self, StumplessError> {
+        let targets = Arc::new(RwLock::new(targets));
+        let chained_targets = Arc::clone(&targets);
+
+        let target = FunctionTarget::new(name, move |entry| {
+            let results = chained_targets
+                .read()
+                .unwrap()
+                .iter()
+                .map(|target| target.add_entry(entry))
+                .collect();
+            CHAIN_RESULTS.with(|chain_results| *chain_results.borrow_mut() = Some(results));
+            Ok(())
+        })?;
+
+        Ok(ChainTarget { target, targets })
+    }
+
+    /// Adds a target to the end of the chain.
+    pub fn push(&self, target: Box<dyn Target + Send>) {
+        self.targets.write().unwrap().push(target);
+    }
+
+    /// The number of targets in the chain.
+    pub fn len(&self) -> usize {
+        self.targets.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.read().unwrap().is_empty()
+    }
+
+    /// Logs an entry to the chain, returning the result from each of its
+    /// targets. An error is only returned if the chain itself failed.
+    pub fn add_entry_report(&self, entry: &Entry) -> Result<ChainReport, StumplessError> {
+        ChainTarget::report(self.target.add_entry(entry))
+    }
+
+    fn report(add_result: Result<u32, StumplessError>) -> Result<ChainReport, StumplessError> {
+        // always taken so that a failed call cannot leave results behind
+        let results = CHAIN_RESULTS.with(|chain_results| chain_results.borrow_mut().take());
+        add_result?;
+
+        Ok(ChainReport {
+            results: results.unwrap_or_default(),
+        })
+    }
+
+    // calls the function on every target, returning the first error
+    fn for_each_target<F>(&self, function: F) -> Result<(), StumplessError>
+    where
+        F: Fn(&dyn Target) -> Result<(), StumplessError>,
+    {
+        self.targets
+            .read()
+            .unwrap()
+            .iter()
+            .map(|target| function(target.as_ref()))
+            .fold(Ok(()), Result::and)
+    }
+}
+
+impl Target for ChainTarget {
+    fn get_pointer(&self) -> *mut stumpless_target {
+        self.target.get_pointer()
+    }
+
+    fn add_entry(&self, entry: &Entry) -> Result<u32, StumplessError> {
+        self.add_entry_report(entry)?.into_result()
+    }
+
+    fn add_message(&self, message: &str) -> Result<u32, StumplessError> {
+        ChainTarget::report(self.target.add_message(message))?.into_result()
+    }
+
+    fn log(&self, severity: Severity, message: &str) -> Result<u32, StumplessError> {
+        ChainTarget::report(self.target.log(severity, message))?.into_result()
+    }
+
+    /// Flushes every target in the chain.
+    fn flush(&self) -> Result<(), StumplessError> {
+        self.for_each_target(|target| target.flush())
+    }
+
+    /// Reopens every target in the chain.
+    fn reopen(&self) -> Result<(), StumplessError> {
+        self.for_each_target(|target| target.reopen())
+    }
+}
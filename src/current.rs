@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use stumpless_sys::stumpless_set_current_target;
+
+use std::ptr;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::error::StumplessError;
+use crate::severity::Severity;
+use crate::target::{DefaultTarget, Target};
+
+// Stumpless makes each newly opened target the current one, so the current
+// target is tracked here instead, which also keeps it open while it is current.
+// The default target is opened the first time it is needed and then shared by
+// every later call that falls back to it.
+struct CurrentTarget {
+    target: Option<Arc<dyn Target + Send>>,
+    default: Option<Arc<dyn Target + Send>>,
+}
+
+static CURRENT_TARGET: RwLock<CurrentTarget> = RwLock::new(CurrentTarget {
+    target: None,
+    default: None,
+});
+
+/// Makes the target the one used by [`log`] and [`current_target`], returning
+/// the target that was current before, if any.
+///
+/// The target is kept open at least until another one is made current or
+/// [`clear_current_target`] is called. It is also made the current target of
+/// the underlying Stumpless library, for any C code in the process that logs
+/// to it.
+pub fn set_current_target(target: Arc<dyn Target + Send>) -> Option<Arc<dyn Target + Send>> {
+    let mut current_target = CURRENT_TARGET
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    unsafe { stumpless_set_current_target(target.get_pointer()) };
+    current_target.target.replace(target)
+}
+
+/// Stops using the current target, returning it if there was one. Afterwards
+/// the default target is used until another target is made current.
+pub fn clear_current_target() -> Option<Arc<dyn Target + Send>> {
+    let mut current_target = CURRENT_TARGET
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    unsafe { stumpless_set_current_target(ptr::null_mut()) };
+    current_target.target.take()
+}
+
+// Points Stumpless back at the current target after another target is opened
+// for internal use, or at nothing if the default target is being used.
+pub(crate) fn restore_current_target() {
+    let current_target = CURRENT_TARGET
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+    let target = match &current_target.target {
+        Some(target) => target.get_pointer(),
+        None => ptr::null_mut(),
+    };
+    unsafe { stumpless_set_current_target(target) };
+}
+
+/// The target set with [`set_current_target`], or the default target if there
+/// is none.
+pub fn current_target() -> Result<Arc<dyn Target + Send>, StumplessError> {
+    let current_target = CURRENT_TARGET
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some(target) = current_target
+        .target
+        .as_ref()
+        .or(current_target.default.as_ref())
+    {
+        return Ok(Arc::clone(target));
+    }
+    drop(current_target);
+
+    // another thread may have set a target or opened the default since
+    let mut current_target = CURRENT_TARGET
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some(target) = &current_target.target {
+        return Ok(Arc::clone(target));
+    }
+
+    let default = match &current_target.default {
+        Some(default) => default,
+        None => current_target
+            .default
+            .insert(Arc::new(DefaultTarget::get_default_target()?)),
+    };
+    Ok(Arc::clone(default))
+}
+
+/// Logs a message to the current target with the given severity.
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use stumpless::{FileTarget, Severity};
+/// stumpless::set_current_target(Arc::new(FileTarget::new("app.log").unwrap()));
+/// stumpless::log(Severity::Info, "logged to app.log").unwrap();
+/// ```
+pub fn log(severity: Severity, message: &str) -> Result<u32, StumplessError> {
+    current_target()?.log(severity, message)
+}
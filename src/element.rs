@@ -0,0 +1,334 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use stumpless_sys::{
+    stumpless_add_new_param, stumpless_add_param, stumpless_copy_element, stumpless_copy_param,
+    stumpless_destroy_element_and_contents, stumpless_destroy_param, stumpless_element,
+    stumpless_element_has_param, stumpless_get_element_name, stumpless_get_param_by_index,
+    stumpless_get_param_by_name, stumpless_get_param_count, stumpless_get_param_name,
+    stumpless_get_param_value, stumpless_get_param_value_by_name, stumpless_new_element,
+    stumpless_new_param, stumpless_param, stumpless_set_param_value,
+    stumpless_set_param_value_by_name,
+};
+
+use crate::error::{debug_field, display_field, last_error, StumplessError};
+use crate::memory::required_string;
+use std::ffi::CString;
+use std::fmt;
+use std::slice;
+
+// The private enterprise number used in the SD-IDs of the elements added by
+// the log and tracing integrations unless they are given another one. This is
+// the number reserved for documentation by RFC 5612.
+#[cfg(any(feature = "log", feature = "tracing"))]
+pub(crate) const DOCUMENTATION_PEN: u32 = 32473;
+
+/// A structured data element, made up of a name and a list of params.
+///
+/// Elements are copied when they are added to an entry, so the same element
+/// can be added to any number of entries and changed afterwards without
+/// affecting them.
+pub struct Element {
+    pub(crate) element: *mut stumpless_element,
+}
+
+impl Element {
+    pub fn new(name: &str) -> Result<Self, StumplessError> {
+        let c_name = CString::new(name)?;
+        let new_element = unsafe { stumpless_new_element(c_name.as_ptr()) };
+
+        if new_element.is_null() {
+            Err(last_error())
+        } else {
+            Ok(Element {
+                element: new_element,
+            })
+        }
+    }
+
+    pub fn name(&self) -> Result<String, StumplessError> {
+        required_string(unsafe { stumpless_get_element_name(self.element) })
+    }
+
+    pub fn param_count(&self) -> usize {
+        unsafe { stumpless_get_param_count(self.element) }
+    }
+
+    pub fn has_param(&self, name: &str) -> bool {
+        match CString::new(name) {
+            Ok(c_name) => unsafe { stumpless_element_has_param(self.element, c_name.as_ptr()) },
+            Err(_) => false,
+        }
+    }
+
+    /// A copy of the first param with the given name.
+    pub fn param(&self, name: &str) -> Result<Param, StumplessError> {
+        Param::copy_of(param_by_name(self.element, name)?)
+    }
+
+    /// Copies of the params of the element, in the order they were added.
+    pub fn params(&self) -> Result<Vec<Param>, StumplessError> {
+        element_params(self.element)
+    }
+
+    /// The value of the first param with the given name.
+    pub fn param_value(&self, name: &str) -> Result<String, StumplessError> {
+        let c_name = CString::new(name)?;
+        required_string(unsafe { stumpless_get_param_value_by_name(self.element, c_name.as_ptr()) })
+    }
+
+    /// Adds a copy of the given param to the end of the element.
+    pub fn add_param(&mut self, param: &Param) -> Result<&mut Self, StumplessError> {
+        add_param_copy(self.element, param)?;
+        Ok(self)
+    }
+
+    pub fn add_new_param(&mut self, name: &str, value: &str) -> Result<&mut Self, StumplessError> {
+        let c_name = CString::new(name)?;
+        let c_value = CString::new(value)?;
+        let add_result =
+            unsafe { stumpless_add_new_param(self.element, c_name.as_ptr(), c_value.as_ptr()) };
+
+        if add_result.is_null() {
+            Err(last_error())
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// Sets the value of the first param with the given name.
+    pub fn set_param_value(
+        &mut self,
+        name: &str,
+        value: &str,
+    ) -> Result<&mut Self, StumplessError> {
+        let c_name = CString::new(name)?;
+        let c_value = CString::new(value)?;
+        let set_result = unsafe {
+            stumpless_set_param_value_by_name(self.element, c_name.as_ptr(), c_value.as_ptr())
+        };
+
+        if set_result.is_null() {
+            Err(last_error())
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// Removes the first param with the given name from the element and
+    /// returns it.
+    pub fn remove_param(&mut self, name: &str) -> Result<Param, StumplessError> {
+        unsafe { remove_param(self.element, name) }
+    }
+}
+
+impl Clone for Element {
+    fn clone(&self) -> Self {
+        let copy = unsafe { stumpless_copy_element(self.element) };
+        if copy.is_null() {
+            panic!("could not copy element: {}", last_error());
+        }
+
+        Element { element: copy }
+    }
+}
+
+impl Drop for Element {
+    fn drop(&mut self) {
+        unsafe {
+            stumpless_destroy_element_and_contents(self.element);
+        }
+    }
+}
+
+unsafe impl Send for Element {}
+unsafe impl Sync for Element {}
+
+impl fmt::Debug for Element {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Element")
+            .field("name", &debug_field(self.name()))
+            .field("params", &debug_field(self.params()))
+            .finish()
+    }
+}
+
+/// Renders the element as it appears in the structured data of an entry.
+///
+/// A name that cannot be read is rendered as the NILVALUE, and params that
+/// cannot be read are left out.
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}", display_field(self.name()))?;
+        for param in self.params().unwrap_or_default() {
+            write!(f, " {}", param)?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// A single name and value pair within a structured data element.
+pub struct Param {
+    pub(crate) param: *mut stumpless_param,
+}
+
+impl Param {
+    pub fn new(name: &str, value: &str) -> Result<Self, StumplessError> {
+        let c_name = CString::new(name)?;
+        let c_value = CString::new(value)?;
+        let new_param = unsafe { stumpless_new_param(c_name.as_ptr(), c_value.as_ptr()) };
+
+        if new_param.is_null() {
+            Err(last_error())
+        } else {
+            Ok(Param { param: new_param })
+        }
+    }
+
+    fn copy_of(param: *const stumpless_param) -> Result<Self, StumplessError> {
+        let copy = unsafe { stumpless_copy_param(param) };
+
+        if copy.is_null() {
+            Err(last_error())
+        } else {
+            Ok(Param { param: copy })
+        }
+    }
+
+    pub fn name(&self) -> Result<String, StumplessError> {
+        required_string(unsafe { stumpless_get_param_name(self.param) })
+    }
+
+    pub fn value(&self) -> Result<String, StumplessError> {
+        required_string(unsafe { stumpless_get_param_value(self.param) })
+    }
+
+    pub fn set_value(&mut self, value: &str) -> Result<&mut Self, StumplessError> {
+        let c_value = CString::new(value)?;
+        let set_result = unsafe { stumpless_set_param_value(self.param, c_value.as_ptr()) };
+
+        if set_result.is_null() {
+            Err(last_error())
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+impl Clone for Param {
+    fn clone(&self) -> Self {
+        Param::copy_of(self.param).unwrap_or_else(|err| panic!("could not copy param: {}", err))
+    }
+}
+
+impl Drop for Param {
+    fn drop(&mut self) {
+        unsafe {
+            stumpless_destroy_param(self.param);
+        }
+    }
+}
+
+unsafe impl Send for Param {}
+unsafe impl Sync for Param {}
+
+impl fmt::Debug for Param {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Param")
+            .field("name", &debug_field(self.name()))
+            .field("value", &debug_field(self.value()))
+            .finish()
+    }
+}
+
+/// Renders the param as `name="value"`, as it appears in the structured data
+/// of an entry.
+impl fmt::Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}=\"{}\"",
+            display_field(self.name()),
+            display_field(self.value())
+        )
+    }
+}
+
+pub(crate) fn param_by_name(
+    element: *const stumpless_element,
+    name: &str,
+) -> Result<*mut stumpless_param, StumplessError> {
+    let c_name = CString::new(name)?;
+    let param = unsafe { stumpless_get_param_by_name(element, c_name.as_ptr()) };
+
+    if param.is_null() {
+        Err(last_error())
+    } else {
+        Ok(param)
+    }
+}
+
+pub(crate) fn element_params(
+    element: *const stumpless_element,
+) -> Result<Vec<Param>, StumplessError> {
+    let param_count = unsafe { stumpless_get_param_count(element) };
+    (0..param_count)
+        .map(|i| {
+            let param = unsafe { stumpless_get_param_by_index(element, i) };
+            if param.is_null() {
+                return Err(last_error());
+            }
+            Param::copy_of(param)
+        })
+        .collect()
+}
+
+pub(crate) fn add_param_copy(
+    element: *mut stumpless_element,
+    param: &Param,
+) -> Result<(), StumplessError> {
+    let copy = Param::copy_of(param.param)?;
+    let add_result = unsafe { stumpless_add_param(element, copy.param) };
+
+    if add_result.is_null() {
+        Err(last_error())
+    } else {
+        // the element owns the param now
+        std::mem::forget(copy);
+        Ok(())
+    }
+}
+
+/// Takes the first param with the given name out of the element.
+///
+/// Stumpless has no way to remove a param from an element, so this is done
+/// by editing the param list directly, bypassing the element's lock.
+///
+/// # Safety
+/// The caller must have exclusive access to the element.
+pub(crate) unsafe fn remove_param(
+    element: *mut stumpless_element,
+    name: &str,
+) -> Result<Param, StumplessError> {
+    let param = param_by_name(element, name)?;
+    let params = slice::from_raw_parts_mut((*element).params, (*element).param_count);
+    let index = params.iter().position(|p| *p == param).unwrap();
+
+    params[index..].rotate_left(1);
+    (*element).param_count -= 1;
+
+    Ok(Param { param })
+}
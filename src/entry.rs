@@ -15,19 +15,49 @@
 // limitations under the License.
 
 use stumpless_sys::{
-    stumpless_add_new_element, stumpless_add_new_param_to_entry,
-    stumpless_destroy_entry_and_contents, stumpless_entry, stumpless_new_entry_str,
-    stumpless_set_entry_prival,
+    stumpless_add_element, stumpless_add_new_element, stumpless_add_new_param_to_entry,
+    stumpless_copy_element, stumpless_destroy_entry_and_contents, stumpless_element,
+    stumpless_entry, stumpless_entry_has_element, stumpless_facility,
+    stumpless_get_element_by_index, stumpless_get_element_by_name, stumpless_get_element_count,
+    stumpless_get_element_name, stumpless_get_entry_app_name, stumpless_get_entry_hostname,
+    stumpless_get_entry_message, stumpless_get_entry_msgid,
+    stumpless_get_entry_param_value_by_name, stumpless_get_entry_prival,
+    stumpless_get_entry_procid, stumpless_new_entry_str, stumpless_set_entry_hostname,
+    stumpless_set_entry_message_str, stumpless_set_entry_param_value_by_name,
+    stumpless_set_entry_prival, stumpless_set_entry_procid, stumpless_severity,
+    STUMPLESS_MAX_APP_NAME_LENGTH, STUMPLESS_MAX_HOSTNAME_LENGTH, STUMPLESS_MAX_MSGID_LENGTH,
+    STUMPLESS_MAX_PROCID_LENGTH,
 };
 
-use crate::error::last_error;
+use crate::element::{add_param_copy, remove_param, Element, Param};
+use crate::error::{debug_field, last_error, ErrorKind, StumplessError};
 use crate::facility::Facility;
+use crate::function::format_entry;
+use crate::memory::required_string;
 use crate::severity::Severity;
-use std::error::Error;
+use std::cell::Cell;
 use std::ffi::CString;
+use std::fmt;
+use std::mem::{self, ManuallyDrop};
+use std::ptr;
+use std::slice;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+/// A single log message, along with its header fields and structured data.
+///
+/// Entries can be shared between threads, for example in an `Arc`. Anything
+/// that changes an entry requires `&mut self`, so a shared entry can only be
+/// read and logged. Wrap it in a `Mutex` if it needs to be changed by more than
+/// one thread.
 pub struct Entry {
-    pub entry: *mut stumpless_entry,
+    pub(crate) entry: *mut stumpless_entry,
+    timestamp: Option<SystemTime>,
+}
+
+thread_local! {
+    // the explicit timestamp of the entry being added on this thread, so that
+    // closures handed the entry back by stumpless can still see it
+    static ADDING_TIMESTAMP: Cell<Option<(usize, SystemTime)>> = const { Cell::new(None) };
 }
 
 impl Entry {
@@ -37,14 +67,14 @@ impl Entry {
         app_name: &str,
         msgid: &str,
         message: &str,
-    ) -> Result<Self, Box<dyn Error>> {
+    ) -> Result<Self, StumplessError> {
         let c_app_name = CString::new(app_name)?;
         let c_msgid = CString::new(msgid)?;
         let c_message = CString::new(message)?;
         let new_entry = unsafe {
             stumpless_new_entry_str(
-                (facility as u32).try_into().unwrap(),
-                (severity as u32).try_into().unwrap(),
+                facility as stumpless_facility,
+                severity as stumpless_severity,
                 c_app_name.as_ptr(),
                 c_msgid.as_ptr(),
                 c_message.as_ptr(),
@@ -52,35 +82,354 @@ impl Entry {
         };
 
         if new_entry.is_null() {
-            match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(Box::new(err)),
+            Err(last_error())
+        } else {
+            Ok(Entry {
+                entry: new_entry,
+                timestamp: None,
+            })
+        }
+    }
+
+    pub fn builder() -> EntryBuilder {
+        EntryBuilder::new()
+    }
+
+    /// Makes a deep copy of the entry, including its structured data.
+    ///
+    /// This is what [`Clone`] uses, but returns an error instead of panicking
+    /// if the copy fails.
+    pub fn try_clone(&self) -> Result<Self, StumplessError> {
+        // stumpless_copy_entry uses the message as a format string, so the copy
+        // is built up from the fields of this entry instead
+        let prival = self.prival();
+        let c_app_name = CString::new(self.app_name()?)?;
+        let c_msgid = CString::new(self.msgid()?)?;
+        let c_message = self.message()?.map(CString::new).transpose()?;
+        let new_entry = unsafe {
+            stumpless_new_entry_str(
+                (prival & 0xf8) as stumpless_facility,
+                (prival & 0x07) as stumpless_severity,
+                c_app_name.as_ptr(),
+                c_msgid.as_ptr(),
+                optional_ptr(&c_message),
+            )
+        };
+
+        if new_entry.is_null() {
+            return Err(last_error());
+        }
+        let copy = Entry {
+            entry: new_entry,
+            timestamp: self.timestamp,
+        };
+
+        // the hostname and procid are only copied if they were set explicitly
+        if unsafe { (*self.entry).hostname_length } > 0 {
+            let c_hostname = CString::new(self.hostname()?)?;
+            if unsafe { stumpless_set_entry_hostname(copy.entry, c_hostname.as_ptr()) }.is_null() {
+                return Err(last_error());
+            }
+        }
+
+        if unsafe { (*self.entry).procid_length } > 0 {
+            let c_procid = CString::new(self.procid()?)?;
+            if unsafe { stumpless_set_entry_procid(copy.entry, c_procid.as_ptr()) }.is_null() {
+                return Err(last_error());
             }
+        }
+
+        for i in 0..self.element_count() {
+            let element = unsafe { stumpless_get_element_by_index(self.entry, i) };
+            if element.is_null() {
+                return Err(last_error());
+            }
+
+            let element = copy_element(element)?;
+            if unsafe { stumpless_add_element(copy.entry, element.element) }.is_null() {
+                return Err(last_error());
+            }
+            // the copy owns the element now
+            mem::forget(element);
+        }
+
+        Ok(copy)
+    }
+
+    // wraps an entry passed to a callback by stumpless, which is only borrowed
+    // for the duration of the call
+    pub(crate) unsafe fn borrowed(entry: *const stumpless_entry) -> ManuallyDrop<Entry> {
+        let timestamp = ADDING_TIMESTAMP
+            .get()
+            .filter(|(adding, _)| *adding == entry as usize)
+            .map(|(_, timestamp)| timestamp);
+
+        ManuallyDrop::new(Entry {
+            entry: entry as *mut stumpless_entry,
+            timestamp,
+        })
+    }
+
+    // runs a call that adds this entry to a target, so that its timestamp is
+    // available to any closures called back during it
+    pub(crate) fn adding<R>(&self, add: impl FnOnce() -> R) -> R {
+        let previous =
+            ADDING_TIMESTAMP.replace(self.timestamp.map(|time| (self.entry as usize, time)));
+        let result = add();
+        ADDING_TIMESTAMP.set(previous);
+        result
+    }
+
+    /// The timestamp of the entry, if one was set explicitly.
+    ///
+    /// Without one the entry is timestamped when it is logged.
+    pub fn timestamp(&self) -> Option<SystemTime> {
+        self.timestamp
+    }
+
+    pub fn set_timestamp(&mut self, timestamp: SystemTime) -> &mut Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn prival(&self) -> i32 {
+        unsafe { stumpless_get_entry_prival(self.entry) }
+    }
+
+    pub fn app_name(&self) -> Result<String, StumplessError> {
+        required_string(unsafe { stumpless_get_entry_app_name(self.entry) })
+    }
+
+    /// The hostname of the entry, which is the system hostname if one has not
+    /// been set explicitly.
+    pub fn hostname(&self) -> Result<String, StumplessError> {
+        required_string(unsafe { stumpless_get_entry_hostname(self.entry) })
+    }
+
+    /// The procid of the entry, which is the current process id if one has not
+    /// been set explicitly.
+    pub fn procid(&self) -> Result<String, StumplessError> {
+        required_string(unsafe { stumpless_get_entry_procid(self.entry) })
+    }
+
+    pub fn msgid(&self) -> Result<String, StumplessError> {
+        required_string(unsafe { stumpless_get_entry_msgid(self.entry) })
+    }
+
+    pub fn message(&self) -> Result<Option<String>, StumplessError> {
+        if unsafe { (*self.entry).message.is_null() } {
+            return Ok(None);
+        }
+
+        required_string(unsafe { stumpless_get_entry_message(self.entry) }).map(Some)
+    }
+
+    pub fn element_count(&self) -> usize {
+        unsafe { stumpless_get_element_count(self.entry) }
+    }
+
+    pub fn has_element(&self, name: &str) -> bool {
+        match CString::new(name) {
+            Ok(c_name) => unsafe { stumpless_entry_has_element(self.entry, c_name.as_ptr()) },
+            Err(_) => false,
+        }
+    }
+
+    pub fn element_names(&self) -> Result<Vec<String>, StumplessError> {
+        (0..self.element_count())
+            .map(|i| {
+                let element = unsafe { stumpless_get_element_by_index(self.entry, i) };
+                if element.is_null() {
+                    return Err(last_error());
+                }
+                required_string(unsafe { stumpless_get_element_name(element) })
+            })
+            .collect()
+    }
+
+    /// A copy of the element with the given name.
+    pub fn element(&self, name: &str) -> Result<Element, StumplessError> {
+        copy_element(self.element_pointer(name)?)
+    }
+
+    /// Copies of the elements of the entry, in the order they were added.
+    pub fn elements(&self) -> Result<Vec<Element>, StumplessError> {
+        (0..self.element_count())
+            .map(|i| {
+                let element = unsafe { stumpless_get_element_by_index(self.entry, i) };
+                if element.is_null() {
+                    return Err(last_error());
+                }
+                copy_element(element)
+            })
+            .collect()
+    }
+
+    pub fn param_value(
+        &self,
+        element_name: &str,
+        param_name: &str,
+    ) -> Result<String, StumplessError> {
+        let c_element_name = CString::new(element_name)?;
+        let c_param_name = CString::new(param_name)?;
+        required_string(unsafe {
+            stumpless_get_entry_param_value_by_name(
+                self.entry,
+                c_element_name.as_ptr(),
+                c_param_name.as_ptr(),
+            )
+        })
+    }
+
+    // the structured data as it appears in a formatted message
+    #[cfg(feature = "sqlite")]
+    pub(crate) fn structured_data(&self) -> Result<String, StumplessError> {
+        let elements = self.elements()?;
+        if elements.is_empty() {
+            return Ok(String::from("-"));
+        }
+
+        Ok(elements.iter().map(Element::to_string).collect())
+    }
+
+    // the procid if it was set explicitly, or the NILVALUE otherwise
+    #[cfg(feature = "sqlite")]
+    pub(crate) fn explicit_procid(&self) -> Result<String, StumplessError> {
+        if unsafe { (*self.entry).procid_length } > 0 {
+            self.procid()
+        } else {
+            Ok(String::from("-"))
+        }
+    }
+
+    fn element_pointer(&self, name: &str) -> Result<*mut stumpless_element, StumplessError> {
+        let c_name = CString::new(name)?;
+        let element = unsafe { stumpless_get_element_by_name(self.entry, c_name.as_ptr()) };
+
+        if element.is_null() {
+            Err(last_error())
+        } else {
+            Ok(element)
+        }
+    }
+
+    /// Adds a copy of the given element to the end of the entry.
+    pub fn add_element(&mut self, element: &Element) -> Result<&mut Self, StumplessError> {
+        let copy = copy_element(element.element)?;
+        let add_result = unsafe { stumpless_add_element(self.entry, copy.element) };
+
+        if add_result.is_null() {
+            Err(last_error())
+        } else {
+            // the entry owns the element now
+            mem::forget(copy);
+            Ok(self)
+        }
+    }
+
+    /// Replaces the element with the same name as the given one with a copy of
+    /// it, keeping its position in the entry.
+    pub fn replace_element(&mut self, element: &Element) -> Result<&mut Self, StumplessError> {
+        let old_element = self.element_pointer(&element.name()?)?;
+        let copy = copy_element(element.element)?;
+
+        // stumpless_set_element rejects an element with the same name as the
+        // one it replaces, so the swap is done on the element list directly
+        let elements = unsafe { self.element_slice() };
+        let index = elements.iter().position(|e| *e == old_element).unwrap();
+        elements[index] = copy.element;
+        mem::forget(copy);
+        drop(Element {
+            element: old_element,
+        });
+
+        Ok(self)
+    }
+
+    /// Removes the element with the given name from the entry and returns it.
+    pub fn remove_element(&mut self, name: &str) -> Result<Element, StumplessError> {
+        let element = self.element_pointer(name)?;
+
+        // Stumpless has no way to remove an element, so this edits the element
+        // list directly, which is safe as this is the only reference to it
+        let elements = unsafe { self.element_slice() };
+        let index = elements.iter().position(|e| *e == element).unwrap();
+        elements[index..].rotate_left(1);
+        unsafe { (*self.entry).element_count -= 1 };
+
+        Ok(Element { element })
+    }
+
+    // the caller must have exclusive access to the entry
+    unsafe fn element_slice(&mut self) -> &mut [*mut stumpless_element] {
+        slice::from_raw_parts_mut((*self.entry).elements, (*self.entry).element_count)
+    }
+
+    /// Adds a copy of the given param to the end of the named element.
+    pub fn add_param(
+        &mut self,
+        element_name: &str,
+        param: &Param,
+    ) -> Result<&mut Self, StumplessError> {
+        add_param_copy(self.element_pointer(element_name)?, param)?;
+        Ok(self)
+    }
+
+    /// Sets the value of the first param with the given name in the named
+    /// element.
+    pub fn set_param_value(
+        &mut self,
+        element_name: &str,
+        param_name: &str,
+        value: &str,
+    ) -> Result<&mut Self, StumplessError> {
+        let c_element_name = CString::new(element_name)?;
+        let c_param_name = CString::new(param_name)?;
+        let c_value = CString::new(value)?;
+        let set_result = unsafe {
+            stumpless_set_entry_param_value_by_name(
+                self.entry,
+                c_element_name.as_ptr(),
+                c_param_name.as_ptr(),
+                c_value.as_ptr(),
+            )
+        };
+
+        if set_result.is_null() {
+            Err(last_error())
         } else {
-            Ok(Entry { entry: new_entry })
+            Ok(self)
         }
     }
 
-    pub fn add_new_element(&self, element: &str) -> Result<&Self, Box<dyn Error>> {
+    /// Removes the first param with the given name from the named element and
+    /// returns it.
+    pub fn remove_param(
+        &mut self,
+        element_name: &str,
+        param_name: &str,
+    ) -> Result<Param, StumplessError> {
+        let element = self.element_pointer(element_name)?;
+        unsafe { remove_param(element, param_name) }
+    }
+
+    pub fn add_new_element(&mut self, element: &str) -> Result<&mut Self, StumplessError> {
         let c_element_name = CString::new(element)?;
         let add_result = unsafe { stumpless_add_new_element(self.entry, c_element_name.as_ptr()) };
 
         if !add_result.is_null() {
             Ok(self)
         } else {
-            match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(Box::new(err)),
-            }
+            Err(last_error())
         }
     }
 
     pub fn add_new_param(
-        &self,
+        &mut self,
         element: &str,
         param_name: &str,
         param_value: &str,
-    ) -> Result<&Self, Box<dyn Error>> {
+    ) -> Result<&mut Self, StumplessError> {
         let c_element_name = CString::new(element)?;
         let c_param_name = CString::new(param_name)?;
         let c_param_value = CString::new(param_value)?;
@@ -96,27 +445,44 @@ impl Entry {
         if !add_result.is_null() {
             Ok(self)
         } else {
-            match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(Box::new(err)),
-            }
+            Err(last_error())
+        }
+    }
+
+    pub fn set_message(&mut self, message: &str) -> Result<&mut Self, StumplessError> {
+        let c_message = CString::new(message)?;
+        let set_result = unsafe { stumpless_set_entry_message_str(self.entry, c_message.as_ptr()) };
+
+        if set_result.is_null() {
+            Err(last_error())
+        } else {
+            Ok(self)
         }
     }
 
-    pub fn set_prival(&self, prival: i32) -> Result<&Entry, Box<dyn Error>> {
+    pub fn set_prival(&mut self, prival: i32) -> Result<&mut Self, StumplessError> {
         let set_result = unsafe { stumpless_set_entry_prival(self.entry, prival) };
 
         if set_result.is_null() {
-            match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(Box::new(err)),
-            }
+            Err(last_error())
         } else {
             Ok(self)
         }
     }
 }
 
+/// Copies the entry with [`Entry::try_clone`].
+///
+/// # Panics
+/// Panics if Stumpless fails to copy the entry, which only happens if memory
+/// cannot be allocated for it. Use `try_clone` to handle this instead.
+impl Clone for Entry {
+    fn clone(&self) -> Self {
+        self.try_clone()
+            .unwrap_or_else(|err| panic!("could not copy entry: {}", err))
+    }
+}
+
 impl Drop for Entry {
     fn drop(&mut self) {
         unsafe {
@@ -127,3 +493,336 @@ impl Drop for Entry {
 
 unsafe impl Send for Entry {}
 unsafe impl Sync for Entry {}
+
+impl fmt::Debug for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Entry")
+            .field("prival", &self.prival())
+            .field("timestamp", &self.timestamp)
+            .field("hostname", &debug_field(self.hostname()))
+            .field("app_name", &debug_field(self.app_name()))
+            .field("procid", &debug_field(self.procid()))
+            .field("msgid", &debug_field(self.msgid()))
+            .field("elements", &debug_field(self.elements()))
+            .field("message", &debug_field(self.message()))
+            .finish()
+    }
+}
+
+/// Renders the entry as the RFC 5424 message that Stumpless would send for it
+/// to a target without the pid option set, without a trailing newline. The
+/// timestamp is the explicit timestamp of the entry, or the current time.
+///
+/// The message is formatted by Stumpless, and formatting fails if it cannot be.
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = format_entry(self, false).map_err(|_| fmt::Error)?;
+        f.write_str(&String::from_utf8_lossy(&message))
+    }
+}
+
+// formats a time the same way as Stumpless, with microsecond precision in UTC
+pub(crate) fn rfc5424_timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = since_epoch.as_secs();
+    let days = (seconds / 86400) as i64;
+    let day_seconds = seconds % 86400;
+
+    // civil date from days since the epoch, from Howard Hinnant's date algorithms
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:06}Z",
+        year,
+        month,
+        day,
+        day_seconds / 3600,
+        (day_seconds % 3600) / 60,
+        day_seconds % 60,
+        since_epoch.subsec_micros()
+    )
+}
+
+/// A fixed set of header fields and structured data that entries are stamped
+/// out from with only the message changed.
+///
+/// Each entry is a copy of the prototype that the template was made from, so
+/// the structured data does not need to be rebuilt for every message.
+#[derive(Clone, Debug)]
+pub struct EntryTemplate {
+    prototype: Entry,
+}
+
+impl EntryTemplate {
+    pub fn new(prototype: Entry) -> Self {
+        EntryTemplate { prototype }
+    }
+
+    pub fn prototype(&self) -> &Entry {
+        &self.prototype
+    }
+
+    /// Creates a new entry from the template with the given message.
+    pub fn entry(&self, message: &str) -> Result<Entry, StumplessError> {
+        let mut entry = self.prototype.try_clone()?;
+        entry.set_message(message)?;
+        Ok(entry)
+    }
+}
+
+impl From<Entry> for EntryTemplate {
+    fn from(prototype: Entry) -> Self {
+        EntryTemplate::new(prototype)
+    }
+}
+
+// the value a hostname or procid takes when it has not been set explicitly
+enum DefaultableField {
+    Default,
+    Nil,
+    Value(String),
+}
+
+/// Builds an [`Entry`] one header field at a time.
+///
+/// Any field that isn't provided will be the RFC 5424 NILVALUE, except for the
+/// hostname and procid which are filled in by Stumpless with the system
+/// hostname and process id unless they are set or explicitly made nil. Note
+/// that Stumpless only includes the procid when the target has the pid option
+/// set.
+///
+/// Entries are timestamped when they are logged unless a timestamp is given,
+/// which not every target supports. See [`EntryBuilder::timestamp`] for the
+/// ones that do.
+pub struct EntryBuilder {
+    facility: Facility,
+    severity: Severity,
+    app_name: Option<String>,
+    hostname: DefaultableField,
+    procid: DefaultableField,
+    msgid: Option<String>,
+    message: Option<String>,
+    timestamp: Option<SystemTime>,
+}
+
+impl EntryBuilder {
+    pub fn new() -> Self {
+        EntryBuilder {
+            facility: Facility::User,
+            severity: Severity::Info,
+            app_name: None,
+            hostname: DefaultableField::Default,
+            procid: DefaultableField::Default,
+            msgid: None,
+            message: None,
+            timestamp: None,
+        }
+    }
+
+    pub fn facility(mut self, facility: Facility) -> Self {
+        self.facility = facility;
+        self
+    }
+
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    pub fn app_name(mut self, app_name: &str) -> Self {
+        self.app_name = Some(app_name.to_string());
+        self
+    }
+
+    pub fn nil_app_name(mut self) -> Self {
+        self.app_name = None;
+        self
+    }
+
+    pub fn hostname(mut self, hostname: &str) -> Self {
+        self.hostname = DefaultableField::Value(hostname.to_string());
+        self
+    }
+
+    pub fn nil_hostname(mut self) -> Self {
+        self.hostname = DefaultableField::Nil;
+        self
+    }
+
+    pub fn procid(mut self, procid: &str) -> Self {
+        self.procid = DefaultableField::Value(procid.to_string());
+        self
+    }
+
+    pub fn nil_procid(mut self) -> Self {
+        self.procid = DefaultableField::Nil;
+        self
+    }
+
+    pub fn msgid(mut self, msgid: &str) -> Self {
+        self.msgid = Some(msgid.to_string());
+        self
+    }
+
+    pub fn nil_msgid(mut self) -> Self {
+        self.msgid = None;
+        self
+    }
+
+    pub fn message(mut self, message: &str) -> Self {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    pub fn no_message(mut self) -> Self {
+        self.message = None;
+        self
+    }
+
+    /// Sets the time that the entry is timestamped with in place of the time
+    /// that it is logged.
+    ///
+    /// Stumpless has no way to carry a timestamp on an entry, so this is only
+    /// used where the message is formatted by this crate. The targets that use
+    /// it are:
+    ///
+    /// * [`FunctionTarget`](crate::FunctionTarget), in the entry passed to the
+    ///   closure and the message passed to a formatted one
+    /// * [`FileTarget`](crate::FileTarget) and
+    ///   [`StreamTarget`](crate::StreamTarget)
+    /// * `NetworkTarget` over TCP with non-transparent framing, and
+    ///   `TlsNetworkTarget`
+    /// * `Sqlite3Target`
+    ///
+    /// The `Display` output of the entry uses it as well. All other targets,
+    /// including buffer, socket, journald, and the default target, as well as
+    /// UDP and octet-counted TCP network targets, ignore it and timestamp the
+    /// entry with the time that it is sent.
+    pub fn timestamp(mut self, timestamp: SystemTime) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Validates the header fields and creates the entry.
+    pub fn build(self) -> Result<Entry, StumplessError> {
+        let c_app_name =
+            validated_optional_field("app name", &self.app_name, STUMPLESS_MAX_APP_NAME_LENGTH)?;
+        let c_msgid = validated_optional_field("msgid", &self.msgid, STUMPLESS_MAX_MSGID_LENGTH)?;
+        let c_message = self.message.map(CString::new).transpose()?;
+        let c_hostname =
+            validated_defaultable_field("hostname", &self.hostname, STUMPLESS_MAX_HOSTNAME_LENGTH)?;
+        let c_procid =
+            validated_defaultable_field("procid", &self.procid, STUMPLESS_MAX_PROCID_LENGTH)?;
+        let new_entry = unsafe {
+            stumpless_new_entry_str(
+                self.facility as stumpless_facility,
+                self.severity as stumpless_severity,
+                optional_ptr(&c_app_name),
+                optional_ptr(&c_msgid),
+                optional_ptr(&c_message),
+            )
+        };
+
+        if new_entry.is_null() {
+            return Err(last_error());
+        }
+        let entry = Entry {
+            entry: new_entry,
+            timestamp: self.timestamp,
+        };
+
+        if let Some(c_hostname) = c_hostname {
+            let set_result =
+                unsafe { stumpless_set_entry_hostname(entry.entry, c_hostname.as_ptr()) };
+            if set_result.is_null() {
+                return Err(last_error());
+            }
+        }
+
+        if let Some(c_procid) = c_procid {
+            let set_result = unsafe { stumpless_set_entry_procid(entry.entry, c_procid.as_ptr()) };
+            if set_result.is_null() {
+                return Err(last_error());
+            }
+        }
+
+        Ok(entry)
+    }
+}
+
+impl Default for EntryBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn optional_ptr(c_str: &Option<CString>) -> *const std::os::raw::c_char {
+    c_str.as_ref().map_or(ptr::null(), |s| s.as_ptr())
+}
+
+// header fields are limited to printable ASCII with a maximum length
+fn validated_field(name: &str, value: &str, max_length: u32) -> Result<CString, StumplessError> {
+    if value.is_empty() {
+        return Err(StumplessError::new(
+            ErrorKind::ArgumentEmpty,
+            &format!("the {} cannot be empty", name),
+        ));
+    }
+
+    if value.len() > max_length as usize {
+        return Err(StumplessError::new(
+            ErrorKind::ArgumentTooBig,
+            &format!("the {} is longer than {} characters", name, max_length),
+        ));
+    }
+
+    if !value.bytes().all(|b| (33..=126).contains(&b)) {
+        return Err(StumplessError::new(
+            ErrorKind::InvalidEncoding,
+            &format!("the {} must only contain printable ASCII characters", name),
+        ));
+    }
+
+    Ok(CString::new(value)?)
+}
+
+fn validated_optional_field(
+    name: &str,
+    value: &Option<String>,
+    max_length: u32,
+) -> Result<Option<CString>, StumplessError> {
+    value
+        .as_deref()
+        .map(|value| validated_field(name, value, max_length))
+        .transpose()
+}
+
+fn validated_defaultable_field(
+    name: &str,
+    value: &DefaultableField,
+    max_length: u32,
+) -> Result<Option<CString>, StumplessError> {
+    match value {
+        DefaultableField::Default => Ok(None),
+        DefaultableField::Nil => Ok(Some(CString::new("-")?)),
+        DefaultableField::Value(value) => validated_field(name, value, max_length).map(Some),
+    }
+}
+
+fn copy_element(element: *const stumpless_element) -> Result<Element, StumplessError> {
+    let copy = unsafe { stumpless_copy_element(element) };
+
+    if copy.is_null() {
+        Err(last_error())
+    } else {
+        Ok(Element { element: copy })
+    }
+}
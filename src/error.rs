@@ -14,67 +14,352 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use stumpless_sys::{stumpless_get_error, stumpless_perror};
+use stumpless_sys::*;
 
 use std::error::Error;
-use std::ffi::{CStr, CString};
+use std::ffi::{CStr, CString, NulError};
 use std::fmt;
+use std::io;
 
+/// The family of failure that a [`StumplessError`] belongs to.
+///
+/// Most of these correspond directly to one of the error ids of the Stumpless
+/// library. The remainder describe failures detected by this crate before the
+/// library was ever called, or errors converted from other sources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    AddressFailure,
+    ArgumentEmpty,
+    ArgumentTooBig,
+    DuplicateElement,
+    ElementNotFound,
+    FileOpenFailure,
+    FileWriteFailure,
+    IndexOutOfBounds,
+    InvalidFacility,
+    InvalidId,
+    InvalidSeverity,
+    MemoryAllocationFailure,
+    NetworkProtocolUnsupported,
+    ParamNotFound,
+    SocketBindFailure,
+    SocketConnectFailure,
+    SocketFailure,
+    SocketSendFailure,
+    StreamWriteFailure,
+    TargetIncompatible,
+    TargetUnsupported,
+    TransportProtocolUnsupported,
+    WindowsEventLogCloseFailure,
+    WindowsEventLogOpenFailure,
+    InvalidEncoding,
+    GethostnameFailure,
+    FunctionTargetFailure,
+    JournaldFailure,
+    WindowsFailure,
+
+    /// A priority value could not be parsed.
+    InvalidPrival,
+
+    /// A server address could not be parsed.
+    InvalidAddress,
+
+    /// A target spec could not be parsed.
+    InvalidTargetSpec,
+
+    /// A framing name could not be parsed.
+    InvalidFraming,
+
+    /// A call to the SQLite3 library failed.
+    Sqlite3Failure,
+
+    /// A logger could not be installed as the global logger.
+    LoggerInitFailure,
+
+    /// The worker thread of an asynchronous target has stopped.
+    WorkerStopped,
+
+    /// The certificates, keys, or settings of a TLS target could not be used.
+    TlsFailure,
+
+    /// An error converted from a [`std::io::Error`].
+    Io,
+
+    /// Stumpless reported a failure, but did not leave an error describing it.
+    Unknown,
+}
+
+impl ErrorKind {
+    #[allow(non_upper_case_globals)]
+    fn from_id(id: stumpless_error_id) -> Self {
+        match id {
+            stumpless_error_id_STUMPLESS_ADDRESS_FAILURE => ErrorKind::AddressFailure,
+            stumpless_error_id_STUMPLESS_ARGUMENT_EMPTY => ErrorKind::ArgumentEmpty,
+            stumpless_error_id_STUMPLESS_ARGUMENT_TOO_BIG => ErrorKind::ArgumentTooBig,
+            stumpless_error_id_STUMPLESS_DUPLICATE_ELEMENT => ErrorKind::DuplicateElement,
+            stumpless_error_id_STUMPLESS_ELEMENT_NOT_FOUND => ErrorKind::ElementNotFound,
+            stumpless_error_id_STUMPLESS_FILE_OPEN_FAILURE => ErrorKind::FileOpenFailure,
+            stumpless_error_id_STUMPLESS_FILE_WRITE_FAILURE => ErrorKind::FileWriteFailure,
+            stumpless_error_id_STUMPLESS_INDEX_OUT_OF_BOUNDS => ErrorKind::IndexOutOfBounds,
+            stumpless_error_id_STUMPLESS_INVALID_FACILITY => ErrorKind::InvalidFacility,
+            stumpless_error_id_STUMPLESS_INVALID_ID => ErrorKind::InvalidId,
+            stumpless_error_id_STUMPLESS_INVALID_SEVERITY => ErrorKind::InvalidSeverity,
+            stumpless_error_id_STUMPLESS_MEMORY_ALLOCATION_FAILURE => {
+                ErrorKind::MemoryAllocationFailure
+            }
+            stumpless_error_id_STUMPLESS_NETWORK_PROTOCOL_UNSUPPORTED => {
+                ErrorKind::NetworkProtocolUnsupported
+            }
+            stumpless_error_id_STUMPLESS_PARAM_NOT_FOUND => ErrorKind::ParamNotFound,
+            stumpless_error_id_STUMPLESS_SOCKET_BIND_FAILURE => ErrorKind::SocketBindFailure,
+            stumpless_error_id_STUMPLESS_SOCKET_CONNECT_FAILURE => ErrorKind::SocketConnectFailure,
+            stumpless_error_id_STUMPLESS_SOCKET_FAILURE => ErrorKind::SocketFailure,
+            stumpless_error_id_STUMPLESS_SOCKET_SEND_FAILURE => ErrorKind::SocketSendFailure,
+            stumpless_error_id_STUMPLESS_STREAM_WRITE_FAILURE => ErrorKind::StreamWriteFailure,
+            stumpless_error_id_STUMPLESS_TARGET_INCOMPATIBLE => ErrorKind::TargetIncompatible,
+            stumpless_error_id_STUMPLESS_TARGET_UNSUPPORTED => ErrorKind::TargetUnsupported,
+            stumpless_error_id_STUMPLESS_TRANSPORT_PROTOCOL_UNSUPPORTED => {
+                ErrorKind::TransportProtocolUnsupported
+            }
+            stumpless_error_id_STUMPLESS_WINDOWS_EVENT_LOG_CLOSE_FAILURE => {
+                ErrorKind::WindowsEventLogCloseFailure
+            }
+            stumpless_error_id_STUMPLESS_WINDOWS_EVENT_LOG_OPEN_FAILURE => {
+                ErrorKind::WindowsEventLogOpenFailure
+            }
+            stumpless_error_id_STUMPLESS_INVALID_ENCODING => ErrorKind::InvalidEncoding,
+            stumpless_error_id_STUMPLESS_GETHOSTNAME_FAILURE => ErrorKind::GethostnameFailure,
+            stumpless_error_id_STUMPLESS_FUNCTION_TARGET_FAILURE => {
+                ErrorKind::FunctionTargetFailure
+            }
+            stumpless_error_id_STUMPLESS_JOURNALD_FAILURE => ErrorKind::JournaldFailure,
+            stumpless_error_id_STUMPLESS_WINDOWS_FAILURE => ErrorKind::WindowsFailure,
+            _ => ErrorKind::Unknown,
+        }
+    }
+
+    fn io_kind(self) -> io::ErrorKind {
+        match self {
+            ErrorKind::ArgumentEmpty
+            | ErrorKind::ArgumentTooBig
+            | ErrorKind::IndexOutOfBounds
+            | ErrorKind::InvalidFacility
+            | ErrorKind::InvalidId
+            | ErrorKind::InvalidSeverity
+            | ErrorKind::InvalidPrival
+            | ErrorKind::InvalidAddress
+            | ErrorKind::InvalidTargetSpec
+            | ErrorKind::InvalidFraming
+            | ErrorKind::DuplicateElement => io::ErrorKind::InvalidInput,
+            ErrorKind::InvalidEncoding => io::ErrorKind::InvalidData,
+            ErrorKind::ElementNotFound | ErrorKind::ParamNotFound => io::ErrorKind::NotFound,
+            ErrorKind::AddressFailure => io::ErrorKind::AddrNotAvailable,
+            ErrorKind::MemoryAllocationFailure => io::ErrorKind::OutOfMemory,
+            ErrorKind::NetworkProtocolUnsupported
+            | ErrorKind::TargetIncompatible
+            | ErrorKind::TargetUnsupported
+            | ErrorKind::TransportProtocolUnsupported => io::ErrorKind::Unsupported,
+            ErrorKind::SocketConnectFailure => io::ErrorKind::ConnectionRefused,
+            ErrorKind::WorkerStopped => io::ErrorKind::BrokenPipe,
+            _ => io::ErrorKind::Other,
+        }
+    }
+
+    fn has_os_code(self) -> bool {
+        matches!(
+            self,
+            ErrorKind::FileOpenFailure
+                | ErrorKind::FileWriteFailure
+                | ErrorKind::SocketBindFailure
+                | ErrorKind::SocketConnectFailure
+                | ErrorKind::SocketFailure
+                | ErrorKind::SocketSendFailure
+                | ErrorKind::StreamWriteFailure
+                | ErrorKind::Io
+        )
+    }
+}
+
+/// An error raised by Stumpless or by the wrappers in this crate.
+///
+/// Errors coming from the library keep the error id that it assigned along
+/// with the code and code type, which typically hold the `errno` (or Windows
+/// equivalent) of the system call that failed.
 #[derive(Debug, Clone)]
 pub struct StumplessError {
-    //id: i32,
-    message: &'static str,
-    //code: i32,
-    //code_type: &'static str,
+    kind: ErrorKind,
+    id: Option<stumpless_error_id>,
+    message: String,
+    code: Option<i32>,
+    code_type: Option<String>,
+}
+
+impl StumplessError {
+    /// Creates a new error that did not come from the Stumpless library.
+    pub fn new(kind: ErrorKind, message: &str) -> Self {
+        StumplessError {
+            kind,
+            id: None,
+            message: message.to_string(),
+            code: None,
+            code_type: None,
+        }
+    }
+
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    pub(crate) fn with_code(kind: ErrorKind, message: &str, code: i32, code_type: &str) -> Self {
+        StumplessError {
+            kind,
+            id: None,
+            message: message.to_string(),
+            code: Some(code),
+            code_type: Some(code_type.to_string()),
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The Stumpless error id, if this error was raised by the library.
+    pub fn id(&self) -> Option<stumpless_error_id> {
+        self.id
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The error code, if one was provided with the error.
+    ///
+    /// The meaning of this value is described by the code type.
+    pub fn code(&self) -> Option<i32> {
+        self.code
+    }
+
+    pub fn code_type(&self) -> Option<&str> {
+        self.code_type.as_deref()
+    }
 }
 
 impl Error for StumplessError {}
 
 impl fmt::Display for StumplessError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str(self.message)
+        f.write_str(&self.message)?;
+        if let (Some(code), Some(code_type)) = (self.code, &self.code_type) {
+            write!(f, " ({}: {})", code_type, code)?;
+        }
+        Ok(())
     }
 }
 
-pub fn invalid_facility_error() -> StumplessError {
-    StumplessError {
-        //id: stumpless_error_id_STUMPLESS_INVALID_FACILITY,
-        message: "invalid facility name",
-        //code: 0,
-        //code_type: "unused",
+impl From<NulError> for StumplessError {
+    fn from(_err: NulError) -> Self {
+        StumplessError::new(
+            ErrorKind::InvalidEncoding,
+            "string contained an interior nul byte",
+        )
     }
 }
 
-pub fn invalid_prival_error() -> StumplessError {
-    StumplessError {
-        //id: 1,
-        message: "invalid prival format",
-        //code: 0,
-        //code_type: "unused",
+impl From<io::Error> for StumplessError {
+    fn from(err: io::Error) -> Self {
+        let code = err.raw_os_error();
+        StumplessError {
+            kind: ErrorKind::Io,
+            id: None,
+            message: err.to_string(),
+            code,
+            code_type: code.map(|_| String::from("os error")),
+        }
+    }
+}
+
+impl From<StumplessError> for io::Error {
+    fn from(err: StumplessError) -> Self {
+        let kind = match err.code {
+            Some(code) if err.kind.has_os_code() => io::Error::from_raw_os_error(code).kind(),
+            _ => err.kind.io_kind(),
+        };
+
+        io::Error::new(kind, err)
+    }
+}
+
+// Formatting an entry reads its fields back out of Stumpless, which can fail.
+// Failed fields are shown with these placeholders instead of failing the whole
+// format, which would make `format!` and `to_string` panic.
+struct Unreadable(StumplessError);
+
+impl fmt::Debug for Unreadable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<unreadable: {}>", self.0)
+    }
+}
+
+pub(crate) fn debug_field<T: fmt::Debug + 'static>(
+    result: Result<T, StumplessError>,
+) -> Box<dyn fmt::Debug> {
+    match result {
+        Ok(value) => Box::new(value),
+        Err(err) => Box::new(Unreadable(err)),
     }
 }
 
+pub(crate) fn display_field(result: Result<String, StumplessError>) -> String {
+    result.unwrap_or_else(|_| String::from("-"))
+}
+
+pub fn invalid_facility_error() -> StumplessError {
+    StumplessError::new(ErrorKind::InvalidFacility, "invalid facility name")
+}
+
+pub fn invalid_prival_error() -> StumplessError {
+    StumplessError::new(ErrorKind::InvalidPrival, "invalid prival format")
+}
+
 pub fn invalid_severity_error() -> StumplessError {
-    StumplessError {
-        //id: stumpless_error_id_STUMPLESS_INVALID_SEVERITY,
-        message: "invalid severity name",
-        //code: 0,
-        //code_type: "unused",
+    StumplessError::new(ErrorKind::InvalidSeverity, "invalid severity name")
+}
+
+fn optional_string(c_str: *const std::os::raw::c_char) -> Option<String> {
+    if c_str.is_null() {
+        None
+    } else {
+        Some(
+            unsafe { CStr::from_ptr(c_str) }
+                .to_string_lossy()
+                .into_owned(),
+        )
     }
 }
 
-pub fn last_error() -> Result<(), StumplessError> {
+/// Gets the error left by the last failed call into Stumpless.
+///
+/// This should only be called after a function indicates that it failed. If
+/// the library did not record an error then one with a kind of
+/// [`ErrorKind::Unknown`] is returned instead.
+pub fn last_error() -> StumplessError {
     let err = unsafe { stumpless_get_error() };
 
     if err.is_null() {
-        Ok(())
-    } else {
-        Err(StumplessError {
-            //id: unsafe { (*err).id },
-            message: unsafe { CStr::from_ptr((*err).message).to_str().unwrap() },
-            //code: unsafe { (*err).code },
-            //code_type: unsafe { CStr::from_ptr((*err).code_type).to_str().unwrap() },
-        })
+        return StumplessError::new(
+            ErrorKind::Unknown,
+            "stumpless reported a failure without an error",
+        );
+    }
+
+    let (id, message, code, code_type) =
+        unsafe { ((*err).id, (*err).message, (*err).code, (*err).code_type) };
+    let code_type = optional_string(code_type);
+    StumplessError {
+        kind: ErrorKind::from_id(id),
+        id: Some(id),
+        message: optional_string(message).unwrap_or_default(),
+        code: code_type.as_ref().map(|_| code),
+        code_type,
     }
 }
 
@@ -16,6 +16,18 @@
 
 use stumpless_sys::*;
 
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{ErrorKind, StumplessError};
+
+/// The facility of a log entry, as defined in RFC 5424.
+///
+/// Facilities parse from their names as used by `logger` and syslog
+/// configuration files, ignoring case, and format back to the canonical form
+/// of these names. They can also be converted to and from their numeric code,
+/// which ranges from 0 for `kern` to 23 for `local7`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Facility {
     Kernel = stumpless_facility_STUMPLESS_FACILITY_KERN as isize,
     User = stumpless_facility_STUMPLESS_FACILITY_USER as isize,
@@ -42,3 +54,126 @@ pub enum Facility {
     Local6 = stumpless_facility_STUMPLESS_FACILITY_LOCAL6 as isize,
     Local7 = stumpless_facility_STUMPLESS_FACILITY_LOCAL7 as isize,
 }
+
+impl Facility {
+    /// The name that this facility is displayed as.
+    pub fn name(self) -> &'static str {
+        match self {
+            Facility::Kernel => "kern",
+            Facility::User => "user",
+            Facility::Mail => "mail",
+            Facility::Daemon => "daemon",
+            Facility::Auth => "auth",
+            Facility::Syslog => "syslog",
+            Facility::Lpr => "lpr",
+            Facility::News => "news",
+            Facility::Uucp => "uucp",
+            Facility::Cron => "cron",
+            Facility::Auth2 => "auth2",
+            Facility::FTP => "ftp",
+            Facility::NTP => "ntp",
+            Facility::Audit => "audit",
+            Facility::Alert => "alert",
+            Facility::Cron2 => "cron2",
+            Facility::Local0 => "local0",
+            Facility::Local1 => "local1",
+            Facility::Local2 => "local2",
+            Facility::Local3 => "local3",
+            Facility::Local4 => "local4",
+            Facility::Local5 => "local5",
+            Facility::Local6 => "local6",
+            Facility::Local7 => "local7",
+        }
+    }
+
+    /// The numeric code of the facility, as used in a prival.
+    pub fn code(self) -> u8 {
+        (self as isize >> 3) as u8
+    }
+}
+
+impl fmt::Display for Facility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Facility {
+    type Err = StumplessError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "kern" => Ok(Facility::Kernel),
+            "user" => Ok(Facility::User),
+            "mail" => Ok(Facility::Mail),
+            "daemon" => Ok(Facility::Daemon),
+            "auth" | "security" => Ok(Facility::Auth),
+            "syslog" => Ok(Facility::Syslog),
+            "lpr" => Ok(Facility::Lpr),
+            "news" => Ok(Facility::News),
+            "uucp" => Ok(Facility::Uucp),
+            "cron" => Ok(Facility::Cron),
+            "auth2" | "authpriv" => Ok(Facility::Auth2),
+            "ftp" => Ok(Facility::FTP),
+            "ntp" => Ok(Facility::NTP),
+            "audit" => Ok(Facility::Audit),
+            "alert" => Ok(Facility::Alert),
+            "cron2" => Ok(Facility::Cron2),
+            "local0" => Ok(Facility::Local0),
+            "local1" => Ok(Facility::Local1),
+            "local2" => Ok(Facility::Local2),
+            "local3" => Ok(Facility::Local3),
+            "local4" => Ok(Facility::Local4),
+            "local5" => Ok(Facility::Local5),
+            "local6" => Ok(Facility::Local6),
+            "local7" => Ok(Facility::Local7),
+            _ => Err(StumplessError::new(
+                ErrorKind::InvalidFacility,
+                &format!("invalid facility name: '{}'", s),
+            )),
+        }
+    }
+}
+
+impl TryFrom<u8> for Facility {
+    type Error = StumplessError;
+
+    fn try_from(code: u8) -> Result<Self, StumplessError> {
+        match code {
+            0 => Ok(Facility::Kernel),
+            1 => Ok(Facility::User),
+            2 => Ok(Facility::Mail),
+            3 => Ok(Facility::Daemon),
+            4 => Ok(Facility::Auth),
+            5 => Ok(Facility::Syslog),
+            6 => Ok(Facility::Lpr),
+            7 => Ok(Facility::News),
+            8 => Ok(Facility::Uucp),
+            9 => Ok(Facility::Cron),
+            10 => Ok(Facility::Auth2),
+            11 => Ok(Facility::FTP),
+            12 => Ok(Facility::NTP),
+            13 => Ok(Facility::Audit),
+            14 => Ok(Facility::Alert),
+            15 => Ok(Facility::Cron2),
+            16 => Ok(Facility::Local0),
+            17 => Ok(Facility::Local1),
+            18 => Ok(Facility::Local2),
+            19 => Ok(Facility::Local3),
+            20 => Ok(Facility::Local4),
+            21 => Ok(Facility::Local5),
+            22 => Ok(Facility::Local6),
+            23 => Ok(Facility::Local7),
+            _ => Err(StumplessError::new(
+                ErrorKind::InvalidFacility,
+                &format!("invalid facility code: {}", code),
+            )),
+        }
+    }
+}
+
+impl From<Facility> for u8 {
+    fn from(facility: Facility) -> Self {
+        facility.code()
+    }
+}
@@ -14,48 +14,76 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use stumpless_sys::*;
+use stumpless_sys::stumpless_target;
 
-use std::error::Error;
-use std::ffi::CString;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 
-use crate::error::last_error;
+use crate::error::StumplessError;
+use crate::function::FunctionTarget;
 use crate::Target;
 
+/// A target that appends each entry to a file as a line.
+///
+/// Messages are buffered, and are written out when the buffer fills up or the
+/// target is flushed, reopened, or closed. Once the file has been renamed, for
+/// example by logrotate, [`Target::reopen`] opens a new file with the original
+/// name for the messages that follow.
 pub struct FileTarget {
-    target: *mut stumpless_target,
+    target: FunctionTarget,
+    filename: String,
+    file: Arc<Mutex<BufWriter<File>>>,
 }
 
 impl FileTarget {
-    pub fn new(filename: &str) -> Result<Self, Box<dyn Error>> {
-        let c_filename = CString::new(filename)?;
-        let file_target = unsafe { stumpless_open_file_target(c_filename.as_ptr()) };
-
-        if file_target.is_null() {
-            match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(Box::new(err)),
-            }
-        } else {
-            Ok(FileTarget {
-                target: file_target,
-            })
-        }
+    pub fn new(filename: &str) -> Result<Self, StumplessError> {
+        let file = Arc::new(Mutex::new(BufWriter::new(open_file(filename)?)));
+
+        let writing = Arc::clone(&file);
+        let target = FunctionTarget::formatted(filename, move |message| {
+            let mut file = lock(&writing);
+            file.write_all(message)?;
+            Ok(file.write_all(b"\n")?)
+        })?;
+
+        Ok(FileTarget {
+            target,
+            filename: filename.to_string(),
+            file,
+        })
     }
 }
 
-unsafe impl Sync for FileTarget {}
+fn open_file(filename: &str) -> Result<File, StumplessError> {
+    Ok(OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(filename)?)
+}
+
+fn lock(file: &Mutex<BufWriter<File>>) -> MutexGuard<'_, BufWriter<File>> {
+    file.lock().unwrap_or_else(PoisonError::into_inner)
+}
 
 impl Target for FileTarget {
     fn get_pointer(&self) -> *mut stumpless_target {
-        self.target
+        self.target.get_pointer()
     }
-}
 
-impl Drop for FileTarget {
-    fn drop(&mut self) {
-        unsafe {
-            stumpless_close_file_target(self.target);
-        }
+    fn flush(&self) -> Result<(), StumplessError> {
+        Ok(lock(&self.file).flush()?)
+    }
+
+    /// Opens the file again by name, after writing out the messages buffered
+    /// for the old one. If the file cannot be opened, the target keeps logging
+    /// to the old one.
+    fn reopen(&self) -> Result<(), StumplessError> {
+        let reopened = open_file(&self.filename)?;
+
+        let mut file = lock(&self.file);
+        let flush_result = file.flush();
+        *file = BufWriter::new(reopened);
+        Ok(flush_result?)
     }
 }
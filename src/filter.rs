@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use stumpless_sys::*;
+
+use std::collections::HashMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+
+use crate::entry::Entry;
+use crate::error::{last_error, StumplessError};
+
+pub(crate) type FilterFunction = dyn Fn(&Entry) -> bool + Send + Sync;
+
+// as with function targets, Stumpless does not pass any user data to a filter,
+// so the closure for each target is looked up by the target's address
+fn filter_functions() -> &'static RwLock<HashMap<usize, Arc<FilterFunction>>> {
+    static FILTER_FUNCTIONS: OnceLock<RwLock<HashMap<usize, Arc<FilterFunction>>>> =
+        OnceLock::new();
+    FILTER_FUNCTIONS.get_or_init(Default::default)
+}
+
+pub(crate) fn set_filter(
+    target: *mut stumpless_target,
+    filter: Arc<FilterFunction>,
+) -> Result<(), StumplessError> {
+    filter_functions()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(target as usize, filter);
+
+    let set_result = unsafe { stumpless_set_target_filter(target, Some(call_filter_function)) };
+    if set_result.is_null() {
+        remove_filter(target);
+        Err(last_error())
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) fn clear_filter(target: *mut stumpless_target) -> Result<(), StumplessError> {
+    let set_result = unsafe { stumpless_set_target_filter(target, Some(stumpless_mask_filter)) };
+    if set_result.is_null() {
+        return Err(last_error());
+    }
+
+    remove_filter(target);
+    Ok(())
+}
+
+/// Forgets the filter closure of a target, if it has one. Targets must call
+/// this before they are closed.
+pub(crate) fn remove_filter(target: *mut stumpless_target) {
+    filter_functions()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .remove(&(target as usize));
+}
+
+unsafe extern "C" fn call_filter_function(
+    target: *const stumpless_target,
+    entry: *const stumpless_entry,
+) -> bool {
+    // replacing the default filter would otherwise ignore the target's mask
+    if !stumpless_mask_filter(target, entry) {
+        return false;
+    }
+
+    let filter = match filter_functions()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&(target as usize))
+    {
+        Some(filter) => Arc::clone(filter),
+        None => return true,
+    };
+
+    let entry = Entry::borrowed(entry);
+
+    // a filter that panics rejects the entry rather than unwinding into C
+    catch_unwind(AssertUnwindSafe(|| filter(&entry))).unwrap_or(false)
+}
@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::{self, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+
+use crate::error::StumplessError;
+use crate::function::FunctionTarget;
+use crate::protocol::{Framing, NetworkProtocol};
+
+pub(crate) type Stream = Box<dyn Write + Send>;
+pub(crate) type Connector = Box<dyn Fn() -> io::Result<Stream> + Send + Sync>;
+
+// frames a formatted message for sending
+fn frame(framing: Framing, message: &[u8]) -> Vec<u8> {
+    match framing {
+        Framing::OctetCounting => {
+            let mut frame = format!("{} ", message.len()).into_bytes();
+            frame.extend_from_slice(message);
+            frame
+        }
+        Framing::NonTransparent => {
+            let mut frame = Vec::with_capacity(message.len() + 1);
+            for &byte in message {
+                match byte {
+                    b'\n' => frame.extend_from_slice(b"#012"),
+                    _ => frame.push(byte),
+                }
+            }
+            frame.push(b'\n');
+            frame
+        }
+    }
+}
+
+struct Connection {
+    connector: Connector,
+    stream: Option<Stream>,
+}
+
+impl Connection {
+    fn connect(&mut self) -> io::Result<()> {
+        self.stream = None;
+        self.stream = Some((self.connector)()?);
+        Ok(())
+    }
+
+    fn send(&mut self, frame: &[u8]) -> io::Result<()> {
+        if self.stream.is_none() {
+            self.connect()?;
+        }
+
+        let stream = self.stream.as_mut().unwrap();
+        let result = stream.write_all(frame).and_then(|()| stream.flush());
+        if result.is_err() {
+            // the next message makes a new connection
+            self.stream = None;
+        }
+
+        result
+    }
+}
+
+// A function target that frames each message itself and writes it to a
+// connection, for transports and framings that Stumpless does not support.
+pub(crate) struct FramedTarget {
+    target: FunctionTarget,
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl FramedTarget {
+    pub(crate) fn new(
+        name: &str,
+        framing: Framing,
+        connector: Connector,
+    ) -> Result<Self, StumplessError> {
+        let mut connection = Connection {
+            connector,
+            stream: None,
+        };
+        connection.connect()?;
+
+        let connection = Arc::new(Mutex::new(connection));
+        let sending = Arc::clone(&connection);
+        let target = FunctionTarget::formatted(name, move |message| {
+            let frame = frame(framing, message);
+            Ok(sending.lock().unwrap().send(&frame)?)
+        })?;
+
+        Ok(FramedTarget { target, connection })
+    }
+
+    pub(crate) fn target(&self) -> &FunctionTarget {
+        &self.target
+    }
+
+    pub(crate) fn reconnect(&self) -> Result<(), StumplessError> {
+        Ok(self.connection.lock().unwrap().connect()?)
+    }
+}
+
+// connects to the first address of the host that uses the IP version, or to
+// the first one that accepts the connection if there is no version
+pub(crate) fn connect_tcp(
+    host: &str,
+    port: &str,
+    network: Option<NetworkProtocol>,
+) -> io::Result<TcpStream> {
+    let port: u16 = port.parse().map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid port '{}'", port),
+        )
+    })?;
+
+    let mut last_error = io::Error::new(
+        io::ErrorKind::AddrNotAvailable,
+        match network {
+            Some(network) => format!("no {:?} address found for '{}'", network, host),
+            None => format!("no address found for '{}'", host),
+        },
+    );
+
+    for address in (host, port).to_socket_addrs()? {
+        let matches_network = match network {
+            Some(NetworkProtocol::Ipv4) => address.is_ipv4(),
+            Some(NetworkProtocol::Ipv6) => address.is_ipv6(),
+            None => true,
+        };
+
+        if matches_network {
+            match TcpStream::connect(address) {
+                Ok(stream) => return Ok(stream),
+                Err(err) => last_error = err,
+            }
+        }
+    }
+
+    Err(last_error)
+}
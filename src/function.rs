@@ -0,0 +1,294 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use stumpless_sys::*;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+
+use crate::current;
+use crate::entry::{rfc5424_timestamp, Entry};
+use crate::error::{last_error, ErrorKind, StumplessError};
+use crate::filter;
+use crate::Target;
+
+type LogFunction =
+    dyn Fn(*const stumpless_target, &Entry) -> Result<(), StumplessError> + Send + Sync;
+
+// Stumpless only passes the target and entry to a log function, so the
+// closure for each target is looked up by the target's address.
+fn log_functions() -> &'static RwLock<HashMap<usize, Arc<LogFunction>>> {
+    static LOG_FUNCTIONS: OnceLock<RwLock<HashMap<usize, Arc<LogFunction>>>> = OnceLock::new();
+    LOG_FUNCTIONS.get_or_init(Default::default)
+}
+
+thread_local! {
+    // the error from the last failed closure on this thread, which is handed
+    // back to the caller in place of the generic function target failure
+    static CLOSURE_ERROR: RefCell<Option<StumplessError>> = const { RefCell::new(None) };
+
+    static FORMATTER: RefCell<Option<Formatter>> = const { RefCell::new(None) };
+}
+
+/// A target that passes each entry to a Rust closure.
+///
+/// The closure decides what to do with the entry, and can reject it by
+/// returning an error. This error is then returned from the call that logged
+/// the entry.
+pub struct FunctionTarget {
+    target: *mut stumpless_target,
+}
+
+impl FunctionTarget {
+    pub fn new<F>(name: &str, log_function: F) -> Result<Self, StumplessError>
+    where
+        F: Fn(&Entry) -> Result<(), StumplessError> + Send + Sync + 'static,
+    {
+        FunctionTarget::open(name, Arc::new(move |_, entry: &Entry| log_function(entry)))
+    }
+
+    /// Creates a target that passes each entry to the closure as a formatted
+    /// RFC 5424 message, without a trailing newline.
+    ///
+    /// The message is formatted by Stumpless, so options of the target such as
+    /// [`TargetOption::Pid`] are respected. Entries with an explicit timestamp
+    /// are formatted with it in place of the time that they are logged.
+    ///
+    /// [`TargetOption::Pid`]: crate::TargetOption::Pid
+    pub fn formatted<F>(name: &str, log_function: F) -> Result<Self, StumplessError>
+    where
+        F: Fn(&[u8]) -> Result<(), StumplessError> + Send + Sync + 'static,
+    {
+        FunctionTarget::open(
+            name,
+            Arc::new(move |target, entry: &Entry| {
+                // the process id is the only option that changes the format
+                let pid = unsafe { stumpless_get_option(target, STUMPLESS_OPTION_PID as c_int) };
+                log_function(&format_entry(entry, pid != 0)?)
+            }),
+        )
+    }
+
+    fn open(name: &str, log_function: Arc<LogFunction>) -> Result<Self, StumplessError> {
+        let c_name = CString::new(name)?;
+        let function_target =
+            unsafe { stumpless_open_function_target(c_name.as_ptr(), Some(call_log_function)) };
+
+        if function_target.is_null() {
+            return Err(last_error());
+        }
+
+        log_functions()
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(function_target as usize, log_function);
+
+        Ok(FunctionTarget {
+            target: function_target,
+        })
+    }
+}
+
+// the size of the first buffer that each thread formats entries into
+const FORMAT_BUFFER_SIZE: usize = 4096;
+
+// Stumpless only formats entries for the targets that it sends them to, so
+// entries are formatted by logging them to a buffer target and reading them
+// straight back out. Each thread keeps its own buffer target, which is replaced
+// by a bigger one whenever an entry does not fit in it.
+struct Formatter {
+    target: *mut stumpless_target,
+    buffer: Box<[u8]>,
+}
+
+impl Formatter {
+    fn new(size: usize) -> Result<Self, StumplessError> {
+        let mut buffer = vec![0u8; size].into_boxed_slice();
+        let c_name = CString::new("formatter")?;
+        let target = unsafe {
+            stumpless_open_buffer_target(
+                c_name.as_ptr(),
+                buffer.as_mut_ptr() as *mut c_char,
+                buffer.len(),
+            )
+        };
+
+        // opening a target makes it the current one in Stumpless
+        current::restore_current_target();
+
+        if target.is_null() {
+            Err(last_error())
+        } else {
+            Ok(Formatter { target, buffer })
+        }
+    }
+
+    fn format(&self, entry: &Entry, pid: bool) -> Result<Vec<u8>, StumplessError> {
+        let option = STUMPLESS_OPTION_PID as c_int;
+        unsafe {
+            if pid {
+                stumpless_set_option(self.target, option);
+            } else {
+                stumpless_unset_option(self.target, option);
+            }
+        }
+
+        if unsafe { stumpless_add_entry(self.target, entry.entry) } < 0 {
+            return Err(last_error());
+        }
+
+        let mut message = vec![0u8; self.buffer.len() + 1];
+        let length = unsafe {
+            stumpless_read_buffer(
+                self.target,
+                message.as_mut_ptr() as *mut c_char,
+                message.len(),
+            )
+        };
+        if length == 0 {
+            return Err(last_error());
+        }
+        message.truncate(length - 1);
+
+        Ok(message)
+    }
+}
+
+impl Drop for Formatter {
+    fn drop(&mut self) {
+        unsafe {
+            stumpless_close_buffer_target(self.target);
+        }
+    }
+}
+
+// Formats the entry as Stumpless would for a target, with the process id
+// included if `pid` is set.
+pub(crate) fn format_entry(entry: &Entry, pid: bool) -> Result<Vec<u8>, StumplessError> {
+    let mut message = FORMATTER.with(|formatter| {
+        let mut formatter = formatter.borrow_mut();
+        loop {
+            let current = match formatter.take() {
+                Some(current) => current,
+                None => Formatter::new(FORMAT_BUFFER_SIZE)?,
+            };
+
+            match current.format(entry, pid) {
+                Err(err) if err.kind() == ErrorKind::ArgumentTooBig => {
+                    *formatter = Some(Formatter::new(current.buffer.len() * 2)?);
+                }
+                result => {
+                    *formatter = Some(current);
+                    return result;
+                }
+            }
+        }
+    })?;
+
+    // Stumpless always uses the current time, which is the second header field
+    if let Some(timestamp) = entry.timestamp() {
+        let fields: Vec<_> = message
+            .splitn(3, |&byte| byte == b' ')
+            .map(<[u8]>::len)
+            .collect();
+        if let [version, time, _] = fields[..] {
+            let start = version + 1;
+            message.splice(
+                start..start + time,
+                rfc5424_timestamp(timestamp).into_bytes(),
+            );
+        }
+    }
+
+    Ok(message)
+}
+
+// Makes a call into Stumpless that may call back into log functions, which
+// leave their error for this to return in place of the generic failure that
+// Stumpless reports. Any error left behind by an earlier call is cleared first
+// so that it cannot be mistaken for one from this call.
+pub(crate) fn dispatch(add: impl FnOnce() -> c_int) -> Result<u32, StumplessError> {
+    CLOSURE_ERROR.with(|closure_error| closure_error.borrow_mut().take());
+    let add_result = add();
+    let closure_error = CLOSURE_ERROR.with(|closure_error| closure_error.borrow_mut().take());
+
+    if add_result >= 0 {
+        Ok(add_result.try_into().unwrap())
+    } else {
+        Err(closure_error.unwrap_or_else(last_error))
+    }
+}
+
+unsafe extern "C" fn call_log_function(
+    target: *const stumpless_target,
+    entry: *const stumpless_entry,
+) -> c_int {
+    let log_function = match log_functions()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&(target as usize))
+    {
+        Some(log_function) => Arc::clone(log_function),
+        None => return -1,
+    };
+
+    let entry = Entry::borrowed(entry);
+
+    let result = match catch_unwind(AssertUnwindSafe(|| log_function(target, &entry))) {
+        Ok(result) => result,
+        Err(_) => Err(StumplessError::new(
+            ErrorKind::FunctionTargetFailure,
+            "the log function panicked",
+        )),
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(err) => {
+            CLOSURE_ERROR.with(|closure_error| *closure_error.borrow_mut() = Some(err));
+            -1
+        }
+    }
+}
+
+unsafe impl Send for FunctionTarget {}
+unsafe impl Sync for FunctionTarget {}
+
+impl Target for FunctionTarget {
+    fn get_pointer(&self) -> *mut stumpless_target {
+        self.target
+    }
+}
+
+impl Drop for FunctionTarget {
+    fn drop(&mut self) {
+        // removed first so that a new target opened at the same address
+        // cannot have its closure removed by this one
+        log_functions()
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&(self.target as usize));
+        filter::remove_filter(self.target);
+
+        unsafe {
+            stumpless_close_function_target(self.target);
+        }
+    }
+}
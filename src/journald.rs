@@ -16,26 +16,26 @@
 
 use stumpless_sys::*;
 
-use std::error::Error;
 use std::ffi::CString;
 
-use crate::error::last_error;
+use crate::error::{last_error, StumplessError};
+use crate::filter;
 use crate::Target;
 
+/// A target that sends entries to the systemd journal.
 pub struct JournaldTarget {
     target: *mut stumpless_target,
 }
 
 impl JournaldTarget {
-    pub fn new() -> Result<Self, Box<dyn Error>> {
-        let target_name = CString::new("stumpless-cli")?;
-        let journald_target = unsafe { stumpless_open_journald_target(target_name.as_ptr()) };
+    /// Opens a target with the given name, which is not used as the app name
+    /// of the entries logged to it.
+    pub fn new(name: &str) -> Result<Self, StumplessError> {
+        let c_name = CString::new(name)?;
+        let journald_target = unsafe { stumpless_open_journald_target(c_name.as_ptr()) };
 
         if journald_target.is_null() {
-            match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(Box::new(err)),
-            }
+            Err(last_error())
         } else {
             Ok(JournaldTarget {
                 target: journald_target,
@@ -44,6 +44,7 @@ impl JournaldTarget {
     }
 }
 
+unsafe impl Send for JournaldTarget {}
 unsafe impl Sync for JournaldTarget {}
 
 impl Target for JournaldTarget {
@@ -54,6 +55,7 @@ impl Target for JournaldTarget {
 
 impl Drop for JournaldTarget {
     fn drop(&mut self) {
+        filter::remove_filter(self.target);
         unsafe {
             stumpless_close_journald_target(self.target);
         }
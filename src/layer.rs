@@ -0,0 +1,244 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::element::{Element, DOCUMENTATION_PEN};
+use crate::entry::Entry;
+use crate::error::StumplessError;
+use crate::facility::Facility;
+use crate::severity::Severity;
+use crate::target::Target;
+
+/// A [`tracing_subscriber`] layer that sends events to a Stumpless target.
+///
+/// Each event becomes an entry with a severity matching its level. The
+/// `message` field of the event is used as the message of the entry, and its
+/// other fields are added as params of an element named `fields@<PEN>`. Each
+/// span that the event is in adds an element named `span.<name>@<PEN>` with
+/// the fields of that span, from the root span out to the current one, and the
+/// name of the current span is used as the msgid.
+///
+/// The private enterprise number in the element names is 32473, which RFC 5612
+/// reserves for documentation, unless another one is set with
+/// [`StumplessLayer::enterprise_number`].
+///
+/// Names that are not valid in structured data or a msgid are left out of
+/// the entry, as are repeats of a span that is already in the stack. Events
+/// that cannot be logged are dropped.
+pub struct StumplessLayer<T: Target> {
+    target: T,
+    facility: Facility,
+    app_name: Option<String>,
+    enterprise_number: u32,
+}
+
+impl<T: Target + Send + Sync + 'static> StumplessLayer<T> {
+    /// Creates a layer for the target that uses the user facility.
+    pub fn new(target: T) -> Self {
+        StumplessLayer {
+            target,
+            facility: Facility::User,
+            app_name: None,
+            enterprise_number: DOCUMENTATION_PEN,
+        }
+    }
+
+    pub fn facility(mut self, facility: Facility) -> Self {
+        self.facility = facility;
+        self
+    }
+
+    pub fn app_name(mut self, app_name: &str) -> Self {
+        self.app_name = Some(app_name.to_string());
+        self
+    }
+
+    /// Sets the private enterprise number used in the names of the elements
+    /// added to each entry.
+    pub fn enterprise_number(mut self, enterprise_number: u32) -> Self {
+        self.enterprise_number = enterprise_number;
+        self
+    }
+
+    fn entry<S>(&self, event: &Event, ctx: &Context<S>) -> Result<Entry, StumplessError>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        let mut fields = FieldVisitor::default();
+        event.record(&mut fields);
+
+        let scope: Vec<_> = ctx
+            .event_scope(event)
+            .map(|scope| scope.from_root().collect())
+            .unwrap_or_default();
+
+        let mut builder = Entry::builder()
+            .facility(self.facility)
+            .severity(level_severity(event.metadata().level()));
+        if let Some(app_name) = &self.app_name {
+            builder = builder.app_name(app_name);
+        }
+        if let Some(span) = scope.last().filter(|span| is_valid_msgid(span.name())) {
+            builder = builder.msgid(span.name());
+        }
+        if let Some(message) = &fields.message {
+            builder = builder.message(message);
+        }
+        let mut entry = builder.build()?;
+
+        if !fields.params.is_empty() {
+            let name = format!("fields@{}", self.enterprise_number);
+            if let Ok(element) = fields_element(&name, &fields.params) {
+                entry.add_element(&element)?;
+            }
+        }
+
+        for span in &scope {
+            let name = format!("span.{}@{}", span.name(), self.enterprise_number);
+            if entry.has_element(&name) {
+                continue;
+            }
+
+            let extensions = span.extensions();
+            let params = extensions
+                .get::<SpanFields>()
+                .map(|span_fields| span_fields.0.as_slice())
+                .unwrap_or_default();
+            if let Ok(element) = fields_element(&name, params) {
+                entry.add_element(&element)?;
+            }
+        }
+
+        Ok(entry)
+    }
+}
+
+impl<S, T> Layer<S> for StumplessLayer<T>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    T: Target + Send + Sync + 'static,
+{
+    fn on_new_span(&self, attrs: &Attributes, id: &Id, ctx: Context<S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut fields = FieldVisitor::default();
+            attrs.record(&mut fields);
+            span.extensions_mut()
+                .insert(SpanFields(fields.into_params()));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record, ctx: Context<S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut fields = FieldVisitor::default();
+            values.record(&mut fields);
+
+            let mut extensions = span.extensions_mut();
+            match extensions.get_mut::<SpanFields>() {
+                Some(span_fields) => span_fields.update(fields.into_params()),
+                None => extensions.insert(SpanFields(fields.into_params())),
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event, ctx: Context<S>) {
+        if let Ok(entry) = self.entry(event, &ctx) {
+            let _ = self.target.add_entry(&entry);
+        }
+    }
+}
+
+// the fields recorded for a span so far, kept in the span's extensions
+struct SpanFields(Vec<(String, String)>);
+
+impl SpanFields {
+    fn update(&mut self, params: Vec<(String, String)>) {
+        for (name, value) in params {
+            match self.0.iter_mut().find(|(existing, _)| *existing == name) {
+                Some((_, existing_value)) => *existing_value = value,
+                None => self.0.push((name, value)),
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    message: Option<String>,
+    params: Vec<(String, String)>,
+}
+
+impl FieldVisitor {
+    // the message of a span is not treated specially, so it is kept as a param
+    fn into_params(mut self) -> Vec<(String, String)> {
+        if let Some(message) = self.message.take() {
+            self.params.insert(0, ("message".to_string(), message));
+        }
+
+        self.params
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = Some(value.to_string());
+        } else {
+            self.params
+                .push((field.name().to_string(), value.to_string()));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = Some(format!("{:?}", value));
+        } else {
+            self.params
+                .push((field.name().to_string(), format!("{:?}", value)));
+        }
+    }
+}
+
+fn level_severity(level: &Level) -> Severity {
+    match *level {
+        Level::ERROR => Severity::Error,
+        Level::WARN => Severity::Warning,
+        Level::INFO => Severity::Info,
+        _ => Severity::Debug,
+    }
+}
+
+// a msgid is 1 to 32 printable ASCII characters, as in RFC 5424
+fn is_valid_msgid(msgid: &str) -> bool {
+    (1..=32).contains(&msgid.len()) && msgid.bytes().all(|b| b.is_ascii_graphic())
+}
+
+// params with names that are not valid are skipped rather than failing the
+// whole element
+fn fields_element(name: &str, params: &[(String, String)]) -> Result<Element, StumplessError> {
+    let mut element = Element::new(name)?;
+    for (param_name, value) in params {
+        let _ = element.add_new_param(param_name, value);
+    }
+
+    Ok(element)
+}
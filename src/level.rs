@@ -0,0 +1,141 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::severity::Severity;
+
+/// The least severe level that [`Target::log`], the per-level helpers, and the
+/// `stump_*!` macros will log, as chosen by the `max-level-*` features.
+///
+/// Calls for less severe levels do nothing and return `Ok(0)`. As this is a
+/// constant, the compiler removes them entirely, including the formatting of
+/// macro arguments. If more than one of the features is enabled, the most
+/// restrictive one is used.
+///
+/// [`Target::log`]: crate::Target::log
+pub const MAX_LEVEL: Severity = if cfg!(feature = "max-level-emerg") {
+    Severity::Emergency
+} else if cfg!(feature = "max-level-alert") {
+    Severity::Alert
+} else if cfg!(feature = "max-level-crit") {
+    Severity::Critical
+} else if cfg!(feature = "max-level-err") {
+    Severity::Error
+} else if cfg!(feature = "max-level-warning") {
+    Severity::Warning
+} else if cfg!(feature = "max-level-notice") {
+    Severity::Notice
+} else if cfg!(feature = "max-level-info") {
+    Severity::Info
+} else {
+    Severity::Debug
+};
+
+/// Whether messages of the given severity are logged with this build.
+#[inline]
+pub const fn level_enabled(severity: Severity) -> bool {
+    severity as u8 <= MAX_LEVEL as u8
+}
+
+/// Logs a formatted message to a target with the given severity.
+///
+/// ```no_run
+/// # use stumpless::{stump, Severity, StreamTarget};
+/// let target = StreamTarget::stdout("example").unwrap();
+/// stump!(target, Severity::Notice, "{} widgets processed", 42).unwrap();
+/// ```
+#[macro_export]
+macro_rules! stump {
+    ($target:expr, $severity:expr, $($arg:tt)+) => {{
+        let severity: $crate::Severity = $severity;
+        if $crate::level_enabled(severity) {
+            use $crate::Target as _;
+            $target.log(severity, &::std::format!($($arg)+))
+        } else {
+            ::std::result::Result::<u32, $crate::StumplessError>::Ok(0)
+        }
+    }};
+}
+
+/// Logs a formatted message to a target with the emergency severity.
+#[macro_export]
+macro_rules! stump_emerg {
+    ($target:expr, $($arg:tt)+) => {
+        $crate::stump!($target, $crate::Severity::Emergency, $($arg)+)
+    };
+}
+
+/// Logs a formatted message to a target with the alert severity.
+#[macro_export]
+macro_rules! stump_alert {
+    ($target:expr, $($arg:tt)+) => {
+        $crate::stump!($target, $crate::Severity::Alert, $($arg)+)
+    };
+}
+
+/// Logs a formatted message to a target with the critical severity.
+#[macro_export]
+macro_rules! stump_crit {
+    ($target:expr, $($arg:tt)+) => {
+        $crate::stump!($target, $crate::Severity::Critical, $($arg)+)
+    };
+}
+
+/// Logs a formatted message to a target with the error severity.
+#[macro_export]
+macro_rules! stump_err {
+    ($target:expr, $($arg:tt)+) => {
+        $crate::stump!($target, $crate::Severity::Error, $($arg)+)
+    };
+}
+
+/// Logs a formatted message to a target with the warning severity.
+#[macro_export]
+macro_rules! stump_warn {
+    ($target:expr, $($arg:tt)+) => {
+        $crate::stump!($target, $crate::Severity::Warning, $($arg)+)
+    };
+}
+
+/// Logs a formatted message to a target with the notice severity.
+#[macro_export]
+macro_rules! stump_notice {
+    ($target:expr, $($arg:tt)+) => {
+        $crate::stump!($target, $crate::Severity::Notice, $($arg)+)
+    };
+}
+
+/// Logs a formatted message to a target with the informational severity.
+///
+/// ```no_run
+/// # use stumpless::{stump_info, StreamTarget};
+/// let target = StreamTarget::stdout("example").unwrap();
+/// let id = 7;
+/// stump_info!(target, "user {} logged in", id).unwrap();
+/// ```
+#[macro_export]
+macro_rules! stump_info {
+    ($target:expr, $($arg:tt)+) => {
+        $crate::stump!($target, $crate::Severity::Info, $($arg)+)
+    };
+}
+
+/// Logs a formatted message to a target with the debug severity.
+#[macro_export]
+macro_rules! stump_debug {
+    ($target:expr, $($arg:tt)+) => {
+        $crate::stump!($target, $crate::Severity::Debug, $($arg)+)
+    };
+}
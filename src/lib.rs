@@ -28,6 +28,33 @@
 //! options as the following features.
 //!
 //!
+//! ### Integration Features
+//!
+//! * **log** -
+//!   Provides a `StumplessLogger` that implements the `Log` trait of the
+//!   [`log`](https://docs.rs/log) crate, along with an `init` function to
+//!   install it as the global logger.
+//! * **tracing** -
+//!   Provides a `StumplessLayer` that implements the `Layer` trait of the
+//!   [`tracing-subscriber`](https://docs.rs/tracing-subscriber) crate, so
+//!   that [`tracing`](https://docs.rs/tracing) events can be sent to any
+//!   target.
+//! * **tokio** -
+//!   Provides an `AsyncTarget` trait for logging from async code, along with a
+//!   `WorkerTarget` that logs to any target from a thread of its own so that
+//!   the [`tokio`](https://docs.rs/tokio) executor is never blocked.
+//!
+//!
+//! ### Level Features
+//!
+//! * **max-level-emerg**, **max-level-alert**, **max-level-crit**,
+//!   **max-level-err**, **max-level-warning**, **max-level-notice**,
+//!   **max-level-info** -
+//!   Compiles out calls to `Target::log`, the per-level helpers, and the
+//!   `stump_*!` macros that are less severe than the given level. See
+//!   `MAX_LEVEL` for details.
+//!
+//!
 //! ### Target Features
 //!
 //! * **journald** -
@@ -36,17 +63,34 @@
 //!   Enables targets that can send logs to a server over a network connection.
 //! * **socket** -
 //!   Enables targets that can send logs to Unix sockets.
+//! * **sqlite** -
+//!   Enables targets that can insert logs into SQLite3 databases. This links
+//!   against the SQLite3 library installed on the system.
+//! * **tls** -
+//!   Enables targets that can send logs to a server over TLS as described in
+//!   RFC 5425, using [`rustls`](https://docs.rs/rustls).
 //! * **wel** -
 //!   Enables targets that can send logs to the Windows Event Log.
 
-use regex::Regex;
+mod buffer;
+pub use crate::buffer::BufferTarget;
+
+mod chain;
+pub use crate::chain::{ChainReport, ChainTarget};
+
+mod current;
+pub use crate::current::{clear_current_target, current_target, log, set_current_target};
+
+mod element;
+pub use crate::element::{Element, Param};
 
 mod entry;
-pub use crate::entry::Entry;
+pub use crate::entry::{Entry, EntryBuilder, EntryTemplate};
 
 mod error;
 pub use crate::error::{
-    invalid_facility_error, invalid_prival_error, invalid_severity_error, perror, StumplessError,
+    invalid_facility_error, invalid_prival_error, invalid_severity_error, perror, ErrorKind,
+    StumplessError,
 };
 
 mod facility;
@@ -55,14 +99,46 @@ pub use crate::facility::Facility;
 mod file;
 pub use crate::file::FileTarget;
 
+mod filter;
+
+#[cfg(any(feature = "network", feature = "tls"))]
+mod framed;
+
+mod function;
+pub use crate::function::FunctionTarget;
+
+#[cfg(feature = "tracing")]
+mod layer;
+#[cfg(feature = "tracing")]
+pub use crate::layer::StumplessLayer;
+
+mod level;
+pub use crate::level::{level_enabled, MAX_LEVEL};
+
+#[cfg(feature = "log")]
+mod logger;
+#[cfg(feature = "log")]
+pub use crate::logger::{init, StumplessLogger};
+
+mod memory;
+
+mod prival;
+pub use crate::prival::Prival;
+
+mod protocol;
+pub use crate::protocol::{Framing, NetworkProtocol, TransportProtocol};
+
 mod severity;
 pub use crate::severity::Severity;
 
+mod spec;
+pub use crate::spec::TargetSpec;
+
 mod stream;
 pub use crate::stream::StreamTarget;
 
 mod target;
-pub use crate::target::{DefaultTarget, Target};
+pub use crate::target::{DefaultTarget, Target, TargetOption};
 
 #[cfg(feature = "journald")]
 mod journald;
@@ -72,74 +148,37 @@ pub use crate::journald::JournaldTarget;
 #[cfg(feature = "network")]
 mod network;
 #[cfg(feature = "network")]
-pub use crate::network::NetworkTarget;
+pub use crate::network::{NetworkTarget, NetworkTargetBuilder};
 
 #[cfg(feature = "socket")]
 mod socket;
 #[cfg(feature = "socket")]
 pub use crate::socket::SocketTarget;
 
+#[cfg(feature = "sqlite")]
+mod sqlite3;
+#[cfg(feature = "sqlite")]
+pub use crate::sqlite3::Sqlite3Target;
+
 #[cfg(feature = "wel")]
 mod wel;
 #[cfg(feature = "wel")]
 pub use crate::wel::{add_default_wel_event_source, WelTarget};
 
-// ideally this will become a wrapper for a stumpless native function
-// this will give more flexibility to allowed values, and make the logic of the
-// cli application simpler
+#[cfg(feature = "tls")]
+mod tls;
+#[cfg(feature = "tls")]
+pub use crate::tls::{TlsNetworkTarget, TlsNetworkTargetBuilder};
+
+#[cfg(feature = "tokio")]
+mod worker;
+#[cfg(feature = "tokio")]
+pub use crate::worker::{AsyncTarget, WorkerTarget};
+
+/// Parses a priority string into a prival.
+///
+/// This accepts everything that [`Prival`] does.
+#[deprecated(note = "parse a `Prival` instead")]
 pub fn prival_from_string(priority: &str) -> Result<i32, StumplessError> {
-    if let Ok(prival) = priority.parse::<i32>() {
-        if (0..=191).contains(&prival) {
-            return Ok(prival);
-        }
-    }
-
-    let priority_re = Regex::new(r"^(\w+).(\w+)$").unwrap();
-    match priority_re.captures(priority) {
-        Some(caps) => {
-            let facility = match caps.get(1).unwrap().as_str() {
-                "kern" => 0,
-                "user" => 1,
-                "mail" => 2,
-                "daemon" => 3,
-                "auth" | "security" => 4,
-                "syslog" => 5,
-                "lpr" => 6,
-                "news" => 7,
-                "uucp" => 8,
-                "cron" => 9,
-                "authpriv" => 10,
-                "ftp" => 11,
-                "ntp" => 12,
-                "local0" => 16,
-                "local1" => 17,
-                "local2" => 18,
-                "local3" => 19,
-                "local4" => 20,
-                "local5" => 21,
-                "local6" => 22,
-                "local7" => 23,
-                _ => {
-                    return Err(invalid_facility_error());
-                }
-            };
-
-            let severity = match caps.get(2).unwrap().as_str() {
-                "emerg" | "panic" => 0,
-                "alert" => 1,
-                "crit" => 2,
-                "err" | "error" => 3,
-                "warning" | "warn" => 4,
-                "notice" => 5,
-                "info" => 6,
-                "debug" => 7,
-                _ => {
-                    return Err(invalid_severity_error());
-                }
-            };
-
-            Ok((facility * 8) + severity)
-        }
-        None => Err(invalid_prival_error()),
-    }
+    priority.parse::<Prival>().map(i32::from)
 }
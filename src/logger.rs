@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+use crate::element::DOCUMENTATION_PEN;
+use crate::entry::Entry;
+use crate::error::{ErrorKind, StumplessError};
+use crate::facility::Facility;
+use crate::severity::Severity;
+use crate::target::{DefaultTarget, Target};
+
+/// A [`log`] crate logger that sends records to a Stumpless target.
+///
+/// Each record becomes an entry with a severity matching its level, and with
+/// the record's module path, file, and line added as params of an element
+/// named `log@<PEN>`, when they are known. Trace records are logged with the
+/// debug severity, as there is nothing lower.
+///
+/// The private enterprise number in the element name is 32473, which RFC 5612
+/// reserves for documentation, unless another one is set with
+/// [`StumplessLogger::enterprise_number`].
+///
+/// Records that cannot be logged are dropped, as the `log` crate has no way to
+/// report failures.
+pub struct StumplessLogger<T: Target> {
+    target: T,
+    level: LevelFilter,
+    facility: Facility,
+    app_name: Option<String>,
+    element_name: String,
+}
+
+impl<T: Target + Send + 'static> StumplessLogger<T> {
+    /// Creates a logger for the target that logs records at the info level and
+    /// above, using the user facility.
+    pub fn new(target: T) -> Self {
+        StumplessLogger {
+            target,
+            level: LevelFilter::Info,
+            facility: Facility::User,
+            app_name: None,
+            element_name: format!("log@{}", DOCUMENTATION_PEN),
+        }
+    }
+
+    pub fn level(mut self, level: LevelFilter) -> Self {
+        self.level = level;
+        self
+    }
+
+    pub fn facility(mut self, facility: Facility) -> Self {
+        self.facility = facility;
+        self
+    }
+
+    pub fn app_name(mut self, app_name: &str) -> Self {
+        self.app_name = Some(app_name.to_string());
+        self
+    }
+
+    /// Sets the private enterprise number used in the name of the `log`
+    /// element.
+    pub fn enterprise_number(mut self, enterprise_number: u32) -> Self {
+        self.element_name = format!("log@{}", enterprise_number);
+        self
+    }
+
+    /// Installs this logger as the global logger, and sets the maximum level
+    /// of the `log` crate to match it.
+    pub fn init(self) -> Result<(), StumplessError> {
+        let level = self.level;
+        log::set_boxed_logger(Box::new(self))?;
+        log::set_max_level(level);
+        Ok(())
+    }
+
+    fn entry(&self, record: &Record) -> Result<Entry, StumplessError> {
+        let mut builder = Entry::builder()
+            .facility(self.facility)
+            .severity(level_severity(record.level()))
+            .message(&record.args().to_string());
+        if let Some(app_name) = &self.app_name {
+            builder = builder.app_name(app_name);
+        }
+        let mut entry = builder.build()?;
+
+        if let Some(module_path) = record.module_path() {
+            self.add_source_param(&mut entry, "module", module_path)?;
+        }
+
+        if let Some(file) = record.file() {
+            self.add_source_param(&mut entry, "file", file)?;
+        }
+
+        if let Some(line) = record.line() {
+            self.add_source_param(&mut entry, "line", &line.to_string())?;
+        }
+
+        Ok(entry)
+    }
+
+    fn add_source_param(
+        &self,
+        entry: &mut Entry,
+        name: &str,
+        value: &str,
+    ) -> Result<(), StumplessError> {
+        if !entry.has_element(&self.element_name) {
+            entry.add_new_element(&self.element_name)?;
+        }
+
+        entry.add_new_param(&self.element_name, name, value)?;
+        Ok(())
+    }
+}
+
+impl<T: Target + Send + 'static> Log for StumplessLogger<T> {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        if let Ok(entry) = self.entry(record) {
+            let _ = self.target.add_entry(&entry);
+        }
+    }
+
+    fn flush(&self) {
+        let _ = self.target.flush();
+    }
+}
+
+impl From<SetLoggerError> for StumplessError {
+    fn from(err: SetLoggerError) -> Self {
+        StumplessError::new(ErrorKind::LoggerInitFailure, &err.to_string())
+    }
+}
+
+/// Installs a [`StumplessLogger`] for the default target as the global logger.
+pub fn init() -> Result<(), StumplessError> {
+    StumplessLogger::new(DefaultTarget::get_default_target()?).init()
+}
+
+fn level_severity(level: Level) -> Severity {
+    match level {
+        Level::Error => Severity::Error,
+        Level::Warn => Severity::Warning,
+        Level::Info => Severity::Info,
+        Level::Debug | Level::Trace => Severity::Debug,
+    }
+}
@@ -18,26 +18,92 @@ use clap::{command, crate_version, parser::ValueSource, value_parser, Arg, ArgAc
 use itertools::Itertools;
 use regex::Regex;
 use std::{
+    collections::HashMap,
     sync::Arc,
     thread::{spawn, JoinHandle},
 };
 use stumpless::{
-    perror, prival_from_string, DefaultTarget, Entry, Facility, FileTarget, Severity, StreamTarget,
-    Target,
+    DefaultTarget, Entry, Facility, FileTarget, Framing, Prival, Severity, StreamTarget, Target,
+    TargetSpec, TransportProtocol,
 };
 
 #[cfg(feature = "journald")]
 use stumpless::JournaldTarget;
 
 #[cfg(feature = "network")]
-use stumpless::NetworkTarget;
+use stumpless::{NetworkProtocol, NetworkTarget};
 
 #[cfg(feature = "socket")]
 use stumpless::SocketTarget;
 
+#[cfg(feature = "sqlite")]
+use stumpless::Sqlite3Target;
+
+#[cfg(feature = "tls")]
+use stumpless::TlsNetworkTarget;
+
 #[cfg(feature = "wel")]
 use stumpless::{add_default_wel_event_source, WelTarget};
 
+// the options that a minimum severity can be given for, by their long names
+const MASKABLE_TARGETS: [&str; 14] = [
+    "default",
+    "journald",
+    "log-file",
+    "socket",
+    "sqlite",
+    "stderr",
+    "stdout",
+    "target",
+    "tcp4",
+    "tcp6",
+    "tls",
+    "udp4",
+    "udp6",
+    "windows-event-log",
+];
+
+#[derive(Clone)]
+struct MinSeverity {
+    target: Option<String>,
+    severity: Severity,
+}
+
+fn parse_min_severity(value: &str) -> Result<MinSeverity, String> {
+    let (target, severity) = match value.split_once('=') {
+        Some((target, severity)) => {
+            if !MASKABLE_TARGETS.contains(&target) {
+                return Err(format!(
+                    "unknown target '{}', expected one of: {}",
+                    target,
+                    MASKABLE_TARGETS.join(", ")
+                ));
+            }
+            (Some(target.to_string()), severity)
+        }
+        None => (None, value),
+    };
+
+    let parsed = match severity.parse::<u8>() {
+        Ok(code) => Severity::try_from(code),
+        Err(_) => severity.parse(),
+    };
+
+    match parsed {
+        Ok(severity) => Ok(MinSeverity { target, severity }),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+// sets the mask for a target if a minimum severity was given for it
+fn apply_mask<T: Target + ?Sized>(target: &T, mask: Option<i32>) {
+    if let Some(mask) = mask {
+        target
+            .set_mask(mask)
+            .expect("setting the target mask failed!");
+    }
+}
+
 fn main() {
     let default_long_help = "\
         If no other targets are specified, then the default target will be \
@@ -108,6 +174,24 @@ fn main() {
         .num_args(1..)
         .required_unless_present("install-wel-default-source");
 
+    let min_severity_long_help = "\
+        Entries less severe than this are not logged. The severity may be \
+        given by name or numeric code, as with the level of --priority.\
+        \n\n\
+        To only apply this to one kind of target, prefix the severity with \
+        the long name of that target's option, as in tcp4=err or \
+        log-file=debug. A minimum for a kind of target takes precedence over \
+        one given for all targets, and applies to every target of that kind. \
+        This option can be provided as many times as needed.";
+    let min_severity_arg = Arg::new("min-severity")
+        .long("min-severity")
+        .value_name("[target=]severity")
+        .value_parser(parse_min_severity)
+        .help("Only log to targets if the entry is at least this severe.")
+        .long_help(min_severity_long_help)
+        .required(false)
+        .action(ArgAction::Append);
+
     let msgid_arg = Arg::new("msgid")
         .short('m')
         .long("msgid")
@@ -115,12 +199,44 @@ fn main() {
         .default_value("-")
         .required(false);
 
+    let framing_long_help = "\
+        With octet-counting framing, each message is sent after its length. \
+        With non-transparent framing, each message is followed by a newline, \
+        and any newlines inside of it are escaped as #012. This is used for \
+        every TCP target that does not give a framing of its own, as in \
+        tcp://host?framing=non-transparent.";
+    let framing_arg = Arg::new("framing")
+        .long("framing")
+        .value_name("framing")
+        .value_parser(|value: &str| value.parse::<Framing>())
+        .default_value("octet-counting")
+        .help("How messages are separated on TCP connections.")
+        .long_help(framing_long_help)
+        .required(false);
+
+    let port_long_help = "\
+        This is used for every network target that does not give a port of \
+        its own. A port can be given along with a server as host:port, or as \
+        [address]:port for IPv6 addresses. TLS targets use port 6514 unless \
+        this option is given.";
+    let port_arg = Arg::new("port")
+        .short('P')
+        .long("port")
+        .value_name("port")
+        .default_value("514")
+        .help("The port to send entries to on network servers.")
+        .long_help(port_long_help)
+        .required(false);
+
     let priority_long_help = "\
         The priority may be specified as an integer, in which case it must be \
-        defined as what is specified in RFC 5424 as the prival.\
+        defined as what is specified in RFC 5424 as the prival. The integer \
+        may also be wrapped in angle brackets, as in <134>.\
         \n\n\
         This may also be provided in a human readable format of \
-        <facility>.<level>. Capitalization is ignored.\
+        <facility>.<level>, where either part may be a name or a numeric \
+        code, as in local0.info or 16.6. A level on its own uses the user \
+        facility. Capitalization is ignored.\
         \n\nSeverity levels:\n\
         emerg or panic\n\
         alert\n\
@@ -190,6 +306,18 @@ fn main() {
         .required(false)
         .action(ArgAction::Append);
 
+    let sqlite_long_help = "\
+        The logs table will be created in the database if it does not already \
+        exist, and each entry inserted into it as a row. This option can be \
+        provided as many times as needed to log to multiple databases.";
+    let sqlite_arg = Arg::new("sqlite")
+        .long("sqlite")
+        .value_name("db")
+        .help("Insert the entry into the given SQLite3 database.")
+        .long_help(sqlite_long_help)
+        .required(false)
+        .action(ArgAction::Append);
+
     let stderr_arg = Arg::new("stderr")
         .short('s')
         .long("stderr")
@@ -203,32 +331,91 @@ fn main() {
         .help("Log to stdout.")
         .required(false);
 
+    let target_long_help = "\
+        The target is given as a URI, such as stdout:, stderr:, \
+        file:///var/log/app.log, unix:///dev/log, journald://, \
+        sqlite:///var/log/app.db, or tcp6://[::1]:601. Network targets may \
+        use tcp, tcp4, tcp6, udp, udp4, or udp6 as the scheme, and use the \
+        port given by --port if they do not include one. This option can be \
+        provided as many times as needed to log to multiple targets.";
+    let target_arg = Arg::new("target")
+        .long("target")
+        .value_name("uri")
+        .value_parser(|value: &str| value.parse::<TargetSpec>())
+        .help("Log the entry to the target described by the URI.")
+        .long_help(target_long_help)
+        .required(false)
+        .action(ArgAction::Append);
+
     let tcp4_arg = Arg::new("tcp4")
         .short('T')
         .long("tcp4")
-        .value_name("server")
+        .value_name("server[:port]")
         .help("Send the entry to the given server using TCP over IPv4.")
         .required(false)
         .action(ArgAction::Append);
 
     let tcp6_arg = Arg::new("tcp6")
         .long("tcp6")
-        .value_name("server")
+        .value_name("server[:port]")
         .help("Send the entry to the given server using TCP over IPv6.")
         .required(false)
         .action(ArgAction::Append);
 
+    let tls_long_help = "\
+        Entries are sent using octet counting as described in RFC 5425. The \
+        server's certificate is checked against the Mozilla root certificates \
+        unless --tls-ca-file is given. This option can be provided as many \
+        times as needed to log to multiple servers.";
+    let tls_arg = Arg::new("tls")
+        .long("tls")
+        .value_name("server[:port]")
+        .help("Send the entry to the given server over TLS.")
+        .long_help(tls_long_help)
+        .required(false)
+        .action(ArgAction::Append);
+
+    let tls_ca_file_arg = Arg::new("tls-ca-file")
+        .long("tls-ca-file")
+        .value_name("file")
+        .help("Trust the CA certificates in the PEM file for TLS servers.")
+        .required(false);
+
+    let tls_cert_arg = Arg::new("tls-cert")
+        .long("tls-cert")
+        .value_name("file")
+        .help("Authenticate to TLS servers with the certificate in the PEM file.")
+        .requires("tls-key")
+        .required(false);
+
+    let tls_key_arg = Arg::new("tls-key")
+        .long("tls-key")
+        .value_name("file")
+        .help("The private key of the TLS client certificate, in a PEM file.")
+        .requires("tls-cert")
+        .required(false);
+
+    let tls_server_name_long_help = "\
+        The name is sent to the server using SNI, and checked against its \
+        certificate. By default the host of each server is used.";
+    let tls_server_name_arg = Arg::new("tls-server-name")
+        .long("tls-server-name")
+        .value_name("name")
+        .help("The name to expect TLS servers to have a certificate for.")
+        .long_help(tls_server_name_long_help)
+        .required(false);
+
     let udp4_arg = Arg::new("udp4")
         .short('d')
         .long("udp4")
-        .value_name("server")
+        .value_name("server[:port]")
         .help("Send the entry to the given server using UDP over IPv4.")
         .required(false)
         .action(ArgAction::Append);
 
     let udp6_arg = Arg::new("udp6")
         .long("udp6")
-        .value_name("server")
+        .value_name("server[:port]")
         .help("Send the entry to the given server using UDP over IPv6.")
         .required(false)
         .action(ArgAction::Append);
@@ -264,19 +451,29 @@ fn main() {
         .version(crate_version!())
         .arg(default_arg)
         .arg(file_arg)
+        .arg(framing_arg)
         .arg(id_arg)
         .arg(journald_arg)
         .arg(log_file_arg)
         .arg(message_arg)
+        .arg(min_severity_arg)
         .arg(msgid_arg)
+        .arg(port_arg)
         .arg(priority_arg)
         .arg(sd_id_arg)
         .arg(sd_param_arg)
         .arg(socket_arg)
+        .arg(sqlite_arg)
         .arg(stderr_arg)
         .arg(stdout_arg)
+        .arg(target_arg)
         .arg(tcp4_arg)
         .arg(tcp6_arg)
+        .arg(tls_arg)
+        .arg(tls_ca_file_arg)
+        .arg(tls_cert_arg)
+        .arg(tls_key_arg)
+        .arg(tls_server_name_arg)
         .arg(udp4_arg)
         .arg(udp6_arg)
         .arg(wel_arg)
@@ -306,7 +503,7 @@ fn main() {
         .map(|s| s.as_str());
     let message = Itertools::intersperse(message_iterator, " ").collect::<String>();
 
-    let entry = Entry::new(
+    let mut entry = Entry::new(
         Facility::User,
         Severity::Notice,
         "stumpless-cli",
@@ -362,10 +559,28 @@ fn main() {
 
     if cli_matches.contains_id("priority") {
         let priority = cli_matches.get_one::<String>("priority").unwrap();
-        let prival = prival_from_string(priority).expect("could not parse priority");
-        entry.set_prival(prival).expect("priority invalid");
+        let prival: Prival = priority.parse().expect("could not parse priority");
+        entry.set_prival(prival.into()).expect("priority invalid");
     }
 
+    let mut default_mask = None;
+    let mut target_masks = HashMap::new();
+    if let Some(min_severities) = cli_matches.get_many::<MinSeverity>("min-severity") {
+        for min_severity in min_severities {
+            let mask = min_severity.severity.mask_upto();
+            match &min_severity.target {
+                Some(target) => {
+                    target_masks.insert(target.as_str(), mask);
+                }
+                None => default_mask = Some(mask),
+            }
+        }
+    }
+    let mask_for = |target: &str| target_masks.get(target).copied().or(default_mask);
+
+    let default_port = cli_matches.get_one::<String>("port").unwrap().clone();
+    let default_framing = *cli_matches.get_one::<Framing>("framing").unwrap();
+
     let mut log_threads: Vec<JoinHandle<()>> = Vec::with_capacity(64); // arbitrary size
     let mut default_needed = true;
     let entry_arc = Arc::new(entry);
@@ -373,8 +588,10 @@ fn main() {
     if let Some(true) = cli_matches.get_one::<bool>("stderr") {
         default_needed = false;
         let entry_clone = Arc::clone(&entry_arc);
+        let mask = mask_for("stderr");
         log_threads.push(spawn(move || {
             let stderr_target = StreamTarget::stderr("stderr").unwrap();
+            apply_mask(&stderr_target, mask);
             stderr_target
                 .add_entry(&entry_clone)
                 .expect("logging to stderr failed!");
@@ -384,8 +601,10 @@ fn main() {
     if let Some(true) = cli_matches.get_one::<bool>("stdout") {
         default_needed = false;
         let entry_clone = Arc::clone(&entry_arc);
+        let mask = mask_for("stdout");
         log_threads.push(spawn(move || {
             let stdout_target = StreamTarget::stdout("stdout").unwrap();
+            apply_mask(&stdout_target, mask);
             stdout_target
                 .add_entry(&entry_clone)
                 .expect("logging to stdout failed!");
@@ -397,12 +616,14 @@ fn main() {
             default_needed = false;
             let log_filename = log_file.clone();
             let entry_clone = Arc::clone(&entry_arc);
+            let mask = mask_for("log-file");
             log_threads.push(spawn(move || {
                 match FileTarget::new(&log_filename) {
-                    Err(_error) => perror("opening the file target failed"),
+                    Err(error) => eprintln!("opening the file target failed: {}", error),
                     Ok(target) => {
-                        if let Err(_error) = target.add_entry(&entry_clone) {
-                            perror("logging to the file target failed");
+                        apply_mask(&target, mask);
+                        if let Err(error) = target.add_entry(&entry_clone) {
+                            eprintln!("logging to the file target failed: {}", error);
                         }
                     }
                 };
@@ -410,12 +631,44 @@ fn main() {
         }
     }
 
+    if let Some(specs) = cli_matches.get_many::<TargetSpec>("target") {
+        for spec in specs {
+            default_needed = false;
+            let mut target_spec = spec.clone();
+            if let TargetSpec::Network {
+                transport,
+                port,
+                framing,
+                ..
+            } = &mut target_spec
+            {
+                port.get_or_insert_with(|| default_port.clone());
+                if *transport == TransportProtocol::Tcp {
+                    framing.get_or_insert(default_framing);
+                }
+            }
+            let entry_clone = Arc::clone(&entry_arc);
+            let mask = mask_for("target");
+            log_threads.push(spawn(move || match target_spec.open() {
+                Err(error) => eprintln!("opening the target {} failed: {}", target_spec, error),
+                Ok(target) => {
+                    apply_mask(target.as_ref(), mask);
+                    if let Err(error) = target.add_entry(&entry_clone) {
+                        eprintln!("logging to the target {} failed: {}", target_spec, error);
+                    }
+                }
+            }));
+        }
+    }
+
     #[cfg(feature = "journald")]
     if cli_matches.contains_id("journald") {
         default_needed = false;
         let entry_clone = Arc::clone(&entry_arc);
+        let mask = mask_for("journald");
         log_threads.push(spawn(move || {
-            let journald_target = JournaldTarget::new().unwrap();
+            let journald_target = JournaldTarget::new("journald").unwrap();
+            apply_mask(&journald_target, mask);
             journald_target
                 .add_entry(&entry_clone)
                 .expect("logging to journald failed!");
@@ -433,8 +686,10 @@ fn main() {
             default_needed = false;
             let entry_clone = Arc::clone(&entry_arc);
             let socket_name = socket.clone();
+            let mask = mask_for("socket");
             log_threads.push(spawn(move || {
                 let socket_target = SocketTarget::new(&socket_name).unwrap();
+                apply_mask(&socket_target, mask);
                 socket_target
                     .add_entry(&entry_clone)
                     .expect("logging to socket failed!");
@@ -447,14 +702,48 @@ fn main() {
         eprintln!("socket logging not enabled, ignoring --socket option");
     }
 
+    #[cfg(feature = "sqlite")]
+    if let Some(databases) = cli_matches.get_many::<String>("sqlite") {
+        for database in databases {
+            default_needed = false;
+            let entry_clone = Arc::clone(&entry_arc);
+            let db_filename = database.clone();
+            let mask = mask_for("sqlite");
+            log_threads.push(spawn(move || {
+                let sqlite_target = Sqlite3Target::new(&db_filename).unwrap();
+                apply_mask(&sqlite_target, mask);
+                sqlite_target
+                    .create_default_table()
+                    .expect("creating the logs table failed!");
+                sqlite_target
+                    .add_entry(&entry_clone)
+                    .expect("logging to sqlite failed!");
+            }));
+        }
+    }
+
+    #[cfg(not(feature = "sqlite"))]
+    if cli_matches.contains_id("sqlite") {
+        eprintln!("sqlite logging not enabled, ignoring --sqlite option");
+    }
+
     #[cfg(feature = "network")]
     if let Some(servers) = cli_matches.get_many::<String>("tcp4") {
         for server in servers {
             default_needed = false;
             let entry_clone = Arc::clone(&entry_arc);
             let server_name = server.clone();
+            let port = default_port.clone();
+            let mask = mask_for("tcp4");
             log_threads.push(spawn(move || {
-                let tcp4_target = NetworkTarget::tcp4(&server_name, "514").unwrap();
+                let tcp4_target = NetworkTarget::builder(&server_name)
+                    .network(NetworkProtocol::Ipv4)
+                    .transport(TransportProtocol::Tcp)
+                    .port(&port)
+                    .framing(default_framing)
+                    .build()
+                    .unwrap();
+                apply_mask(&tcp4_target, mask);
                 tcp4_target
                     .add_entry(&entry_clone)
                     .expect("logging to tcp4 failed");
@@ -473,8 +762,17 @@ fn main() {
             default_needed = false;
             let entry_clone = Arc::clone(&entry_arc);
             let server_name = server.clone();
+            let port = default_port.clone();
+            let mask = mask_for("tcp6");
             log_threads.push(spawn(move || {
-                let tcp6_target = NetworkTarget::tcp6(&server_name, "514").unwrap();
+                let tcp6_target = NetworkTarget::builder(&server_name)
+                    .network(NetworkProtocol::Ipv6)
+                    .transport(TransportProtocol::Tcp)
+                    .port(&port)
+                    .framing(default_framing)
+                    .build()
+                    .unwrap();
+                apply_mask(&tcp6_target, mask);
                 tcp6_target
                     .add_entry(&entry_clone)
                     .expect("logging to tcp6 failed");
@@ -493,8 +791,16 @@ fn main() {
             default_needed = false;
             let entry_clone = Arc::clone(&entry_arc);
             let server_name = server.clone();
+            let port = default_port.clone();
+            let mask = mask_for("udp4");
             log_threads.push(spawn(move || {
-                let udp4_target = NetworkTarget::udp4(&server_name, "514").unwrap();
+                let udp4_target = NetworkTarget::builder(&server_name)
+                    .network(NetworkProtocol::Ipv4)
+                    .transport(TransportProtocol::Udp)
+                    .port(&port)
+                    .build()
+                    .unwrap();
+                apply_mask(&udp4_target, mask);
                 udp4_target
                     .add_entry(&entry_clone)
                     .expect("logging to udp4 failed");
@@ -513,8 +819,16 @@ fn main() {
             default_needed = false;
             let entry_clone = Arc::clone(&entry_arc);
             let server_name = server.clone();
+            let port = default_port.clone();
+            let mask = mask_for("udp6");
             log_threads.push(spawn(move || {
-                let udp6_target = NetworkTarget::tcp6(&server_name, "514").unwrap();
+                let udp6_target = NetworkTarget::builder(&server_name)
+                    .network(NetworkProtocol::Ipv6)
+                    .transport(TransportProtocol::Udp)
+                    .port(&port)
+                    .build()
+                    .unwrap();
+                apply_mask(&udp6_target, mask);
                 udp6_target
                     .add_entry(&entry_clone)
                     .expect("logging to udp6 failed");
@@ -527,6 +841,52 @@ fn main() {
         eprintln!("network logging not enabled, ignoring --udp6 option");
     }
 
+    #[cfg(feature = "tls")]
+    if let Some(servers) = cli_matches.get_many::<String>("tls") {
+        let port = match cli_matches.value_source("port") {
+            Some(ValueSource::CommandLine) => default_port.clone(),
+            _ => TlsNetworkTarget::DEFAULT_PORT.to_string(),
+        };
+        let ca_file = cli_matches.get_one::<String>("tls-ca-file").cloned();
+        let cert_file = cli_matches.get_one::<String>("tls-cert").cloned();
+        let key_file = cli_matches.get_one::<String>("tls-key").cloned();
+        let server_name = cli_matches.get_one::<String>("tls-server-name").cloned();
+
+        for server in servers {
+            default_needed = false;
+            let entry_clone = Arc::clone(&entry_arc);
+            let server_address = server.clone();
+            let mut builder = TlsNetworkTarget::builder(server).port(&port);
+            if let Some(ca_file) = &ca_file {
+                builder = builder.ca_file(ca_file);
+            }
+            if let (Some(cert_file), Some(key_file)) = (&cert_file, &key_file) {
+                builder = builder.client_cert(cert_file, key_file);
+            }
+            if let Some(server_name) = &server_name {
+                builder = builder.server_name(server_name);
+            }
+            let mask = mask_for("tls");
+            log_threads.push(spawn(move || match builder.build() {
+                Err(error) => eprintln!(
+                    "opening the tls target {} failed: {}",
+                    server_address, error
+                ),
+                Ok(tls_target) => {
+                    apply_mask(&tls_target, mask);
+                    if let Err(error) = tls_target.add_entry(&entry_clone) {
+                        eprintln!("logging to tls {} failed: {}", server_address, error);
+                    }
+                }
+            }));
+        }
+    }
+
+    #[cfg(not(feature = "tls"))]
+    if cli_matches.contains_id("tls") {
+        eprintln!("tls logging not enabled, ignoring --tls option");
+    }
+
     #[cfg(feature = "wel")]
     if cli_matches.value_source("windows-event-log") == Some(ValueSource::CommandLine) {
         if let Some(wel_logs) = cli_matches.get_many::<String>("windows-event-log") {
@@ -534,8 +894,10 @@ fn main() {
                 default_needed = false;
                 let entry_clone = Arc::clone(&entry_arc);
                 let wel_log_name = wel_log.clone();
+                let mask = mask_for("windows-event-log");
                 log_threads.push(spawn(move || {
                     let wel_target = WelTarget::new(&wel_log_name).unwrap();
+                    apply_mask(&wel_target, mask);
                     wel_target
                         .add_entry(&entry_clone)
                         .expect("logging to the Windows Event Log failed!");
@@ -551,8 +913,10 @@ fn main() {
 
     if cli_matches.contains_id("default") || default_needed {
         let entry_clone = Arc::clone(&entry_arc);
+        let mask = mask_for("default");
         log_threads.push(spawn(move || {
             let default_target = DefaultTarget::get_default_target().unwrap();
+            apply_mask(&default_target, mask);
             default_target
                 .add_entry(&entry_clone)
                 .expect("logging to the default target failed!");
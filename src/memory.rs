@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::{last_error, StumplessError};
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+// Stumpless getters return copies of strings allocated with its configured
+// malloc function, which is the standard library's unless changed with
+// stumpless_set_malloc. This copies them into Rust strings and frees them.
+pub fn take_c_string(c_str: *const c_char) -> Option<String> {
+    if c_str.is_null() {
+        return None;
+    }
+
+    let owned = unsafe { CStr::from_ptr(c_str) }
+        .to_string_lossy()
+        .into_owned();
+    unsafe { libc::free(c_str as *mut libc::c_void) };
+
+    Some(owned)
+}
+
+// a null return from a getter means that the call failed
+pub fn required_string(c_str: *const c_char) -> Result<String, StumplessError> {
+    take_c_string(c_str).ok_or_else(last_error)
+}
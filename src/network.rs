@@ -16,123 +16,306 @@
 
 use stumpless_sys::*;
 
-use std::error::Error;
 use std::ffi::CString;
+use std::sync::{Arc, Mutex};
 
-use crate::error::last_error;
+use crate::entry::Entry;
+use crate::error::{last_error, ErrorKind, StumplessError};
+use crate::filter;
+use crate::framed::{self, FramedTarget};
+use crate::memory::required_string;
+use crate::protocol::{self, Framing, NetworkProtocol, TransportProtocol};
+use crate::severity::Severity;
 use crate::Target;
 
 pub struct NetworkTarget {
-    target: *mut stumpless_target,
+    connection: Connection,
+}
+
+enum Connection {
+    // Stumpless sends the messages itself, using octet counting over TCP
+    Stumpless(StumplessTarget),
+    // for the framings that Stumpless does not support
+    Framed {
+        target: FramedTarget,
+        server: Arc<Mutex<Server>>,
+    },
+}
+
+// a network target of Stumpless, which is closed by the NetworkTarget holding it
+struct StumplessTarget(*mut stumpless_target);
+
+unsafe impl Send for StumplessTarget {}
+unsafe impl Sync for StumplessTarget {}
+
+impl Target for StumplessTarget {
+    fn get_pointer(&self) -> *mut stumpless_target {
+        self.0
+    }
+}
+
+struct Server {
+    host: String,
+    port: String,
+    network: NetworkProtocol,
 }
 
 impl NetworkTarget {
-    pub fn tcp4(server: &str, port: &str) -> Result<Self, Box<dyn Error>> {
-        let server_name = CString::new(server)?;
-        let network_target = unsafe { stumpless_new_tcp4_target(server_name.as_ptr()) };
+    /// The port used by network targets unless another one is given.
+    pub const DEFAULT_PORT: &'static str = "514";
 
-        if network_target.is_null() {
-            return match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(Box::new(err)),
-            };
-        }
+    /// Splits a server address into its host and port, if it has one.
+    ///
+    /// The port is separated from the host with a colon, as in
+    /// `example.com:601`. IPv6 addresses must be put in brackets to have a
+    /// port, as in `[::1]:601`, and are otherwise taken to be a host without
+    /// one.
+    pub fn split_address(address: &str) -> Result<(&str, Option<&str>), StumplessError> {
+        protocol::split_address(address).ok_or_else(|| {
+            StumplessError::new(
+                ErrorKind::InvalidAddress,
+                &format!("invalid server address '{}'", address),
+            )
+        })
+    }
 
-        let tcp_target = NetworkTarget {
-            target: network_target,
-        };
+    /// Starts building a target that sends entries to the given server, which
+    /// may include a port as described in
+    /// [`NetworkTarget::split_address`].
+    ///
+    /// ```no_run
+    /// # use stumpless::{NetworkProtocol, NetworkTarget, TransportProtocol};
+    /// let target = NetworkTarget::builder("collector.example")
+    ///     .network(NetworkProtocol::Ipv6)
+    ///     .transport(TransportProtocol::Tcp)
+    ///     .port("601")
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder(server: &str) -> NetworkTargetBuilder {
+        NetworkTargetBuilder::new(server)
+    }
 
-        tcp_target.set_transport_port(port)?;
-        tcp_target.open()?;
-        Ok(tcp_target)
+    pub fn tcp4(server: &str, port: &str) -> Result<Self, StumplessError> {
+        NetworkTarget::open_target(server, NetworkProtocol::Ipv4, TransportProtocol::Tcp, port)
     }
 
-    pub fn tcp6(server: &str, port: &str) -> Result<Self, Box<dyn Error>> {
+    pub fn tcp6(server: &str, port: &str) -> Result<Self, StumplessError> {
+        NetworkTarget::open_target(server, NetworkProtocol::Ipv6, TransportProtocol::Tcp, port)
+    }
+
+    pub fn udp4(server: &str, port: &str) -> Result<Self, StumplessError> {
+        NetworkTarget::open_target(server, NetworkProtocol::Ipv4, TransportProtocol::Udp, port)
+    }
+
+    pub fn udp6(server: &str, port: &str) -> Result<Self, StumplessError> {
+        NetworkTarget::open_target(server, NetworkProtocol::Ipv6, TransportProtocol::Udp, port)
+    }
+
+    fn open_target(
+        server: &str,
+        network: NetworkProtocol,
+        transport: TransportProtocol,
+        port: &str,
+    ) -> Result<Self, StumplessError> {
         let server_name = CString::new(server)?;
-        let network_target = unsafe { stumpless_new_tcp6_target(server_name.as_ptr()) };
+        let network_target = unsafe {
+            stumpless_new_network_target(
+                server_name.as_ptr(),
+                match network {
+                    NetworkProtocol::Ipv4 => {
+                        stumpless_network_protocol_STUMPLESS_IPV4_NETWORK_PROTOCOL
+                    }
+                    NetworkProtocol::Ipv6 => {
+                        stumpless_network_protocol_STUMPLESS_IPV6_NETWORK_PROTOCOL
+                    }
+                },
+                match transport {
+                    TransportProtocol::Tcp => {
+                        stumpless_transport_protocol_STUMPLESS_TCP_TRANSPORT_PROTOCOL
+                    }
+                    TransportProtocol::Udp => {
+                        stumpless_transport_protocol_STUMPLESS_UDP_TRANSPORT_PROTOCOL
+                    }
+                },
+            )
+        };
 
         if network_target.is_null() {
-            return match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(Box::new(err)),
-            };
+            return Err(last_error());
         }
 
-        let tcp_target = NetworkTarget {
-            target: network_target,
+        let target = NetworkTarget {
+            connection: Connection::Stumpless(StumplessTarget(network_target)),
         };
 
-        tcp_target.set_transport_port(port)?;
-        tcp_target.open()?;
-        Ok(tcp_target)
+        target.set_transport_port(port)?;
+        target.open()?;
+        Ok(target)
     }
 
-    pub fn udp4(server: &str, port: &str) -> Result<Self, Box<dyn Error>> {
-        let server_name = CString::new(server)?;
-        let network_target = unsafe { stumpless_new_udp4_target(server_name.as_ptr()) };
+    fn open_framed(
+        server: &str,
+        network: NetworkProtocol,
+        framing: Framing,
+        port: &str,
+    ) -> Result<Self, StumplessError> {
+        let server = Arc::new(Mutex::new(Server {
+            host: server.to_string(),
+            port: port.to_string(),
+            network,
+        }));
 
-        if network_target.is_null() {
-            return match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(Box::new(err)),
-            };
+        let connecting = Arc::clone(&server);
+        let connector = Box::new(move || -> std::io::Result<framed::Stream> {
+            let server = connecting.lock().unwrap();
+            let stream = framed::connect_tcp(&server.host, &server.port, Some(server.network))?;
+            Ok(Box::new(stream))
+        });
+
+        let host = server.lock().unwrap().host.clone();
+        let target = FramedTarget::new(&host, framing, connector)?;
+        Ok(NetworkTarget {
+            connection: Connection::Framed { target, server },
+        })
+    }
+
+    /// Changes the port that entries are sent to, reconnecting to the server
+    /// if needed.
+    pub fn set_transport_port(&self, port: &str) -> Result<(), StumplessError> {
+        match &self.connection {
+            Connection::Stumpless(target) => {
+                let port_name = CString::new(port)?;
+                let port_result =
+                    unsafe { stumpless_set_transport_port(target.0, port_name.as_ptr()) };
+                if port_result.is_null() {
+                    Err(last_error())
+                } else {
+                    Ok(())
+                }
+            }
+            Connection::Framed { target, server } => {
+                server.lock().unwrap().port = port.to_string();
+                target.reconnect()
+            }
         }
+    }
 
-        let udp_target = NetworkTarget {
-            target: network_target,
-        };
+    pub fn get_transport_port(&self) -> Result<String, StumplessError> {
+        match &self.connection {
+            Connection::Stumpless(target) => {
+                required_string(unsafe { stumpless_get_transport_port(target.0) })
+            }
+            Connection::Framed { server, .. } => Ok(server.lock().unwrap().port.clone()),
+        }
+    }
 
-        udp_target.set_transport_port(port)?;
-        udp_target.open()?;
-        Ok(udp_target)
+    // the target that entries are logged to
+    fn inner(&self) -> &dyn Target {
+        match &self.connection {
+            Connection::Stumpless(target) => target,
+            Connection::Framed { target, .. } => target.target(),
+        }
     }
+}
 
-    pub fn udp6(server: &str, port: &str) -> Result<Self, Box<dyn Error>> {
-        let server_name = CString::new(server)?;
-        let network_target = unsafe { stumpless_new_udp6_target(server_name.as_ptr()) };
+/// Builds a [`NetworkTarget`], which is opened once it is built.
+///
+/// Unless they are changed, targets use UDP over IPv4 and port 514, and TCP
+/// targets use octet counting.
+pub struct NetworkTargetBuilder {
+    server: String,
+    network: NetworkProtocol,
+    transport: TransportProtocol,
+    port: String,
+    framing: Framing,
+}
 
-        if network_target.is_null() {
-            return match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(Box::new(err)),
-            };
+impl NetworkTargetBuilder {
+    pub fn new(server: &str) -> Self {
+        NetworkTargetBuilder {
+            server: server.to_string(),
+            network: NetworkProtocol::Ipv4,
+            transport: TransportProtocol::Udp,
+            port: NetworkTarget::DEFAULT_PORT.to_string(),
+            framing: Framing::OctetCounting,
         }
+    }
 
-        let udp_target = NetworkTarget {
-            target: network_target,
-        };
+    pub fn network(mut self, network: NetworkProtocol) -> Self {
+        self.network = network;
+        self
+    }
+
+    pub fn transport(mut self, transport: TransportProtocol) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Sets the port to send entries to if the server does not include one.
+    pub fn port(mut self, port: &str) -> Self {
+        self.port = port.to_string();
+        self
+    }
 
-        udp_target.set_transport_port(port)?;
-        udp_target.open()?;
-        Ok(udp_target)
+    /// Sets how messages are separated from each other over TCP. This has no
+    /// effect on UDP targets, which send each message in its own datagram.
+    pub fn framing(mut self, framing: Framing) -> Self {
+        self.framing = framing;
+        self
     }
 
-    fn set_transport_port(&self, port: &str) -> Result<(), Box<dyn Error>> {
-        let port_name = CString::new(port)?;
-        let port_result = unsafe { stumpless_set_transport_port(self.target, port_name.as_ptr()) };
-        if port_result.is_null() {
-            match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(Box::new(err)),
+    /// Opens the target, connecting to the server if the transport needs it.
+    pub fn build(self) -> Result<NetworkTarget, StumplessError> {
+        let (host, port) = NetworkTarget::split_address(&self.server)?;
+        let port = port.unwrap_or(&self.port);
+
+        match (self.transport, self.framing) {
+            (TransportProtocol::Tcp, Framing::NonTransparent) => {
+                NetworkTarget::open_framed(host, self.network, self.framing, port)
             }
-        } else {
-            Ok(())
+            _ => NetworkTarget::open_target(host, self.network, self.transport, port),
         }
     }
 }
 
-unsafe impl Sync for NetworkTarget {}
-
 impl Target for NetworkTarget {
     fn get_pointer(&self) -> *mut stumpless_target {
-        self.target
+        self.inner().get_pointer()
+    }
+
+    fn add_entry(&self, entry: &Entry) -> Result<u32, StumplessError> {
+        self.inner().add_entry(entry)
+    }
+
+    fn add_message(&self, message: &str) -> Result<u32, StumplessError> {
+        self.inner().add_message(message)
+    }
+
+    fn log(&self, severity: Severity, message: &str) -> Result<u32, StumplessError> {
+        self.inner().log(severity, message)
+    }
+
+    fn open(&self) -> Result<(), StumplessError> {
+        match &self.connection {
+            Connection::Stumpless(target) => target.open(),
+            Connection::Framed { target, .. } => target.reconnect(),
+        }
+    }
+
+    fn reopen(&self) -> Result<(), StumplessError> {
+        self.open()
     }
 }
 
 impl Drop for NetworkTarget {
     fn drop(&mut self) {
-        unsafe {
-            stumpless_close_network_target(self.target);
+        // framed targets are closed along with their function target
+        if let Connection::Stumpless(target) = &self.connection {
+            filter::remove_filter(target.0);
+            unsafe {
+                stumpless_close_network_target(target.0);
+            }
         }
     }
 }
@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{ErrorKind, StumplessError};
+use crate::facility::Facility;
+use crate::severity::Severity;
+
+/// The priority value of an entry, which combines its facility and severity.
+///
+/// A prival can be parsed from any of these forms:
+///
+/// * a number from 0 to 191, such as `134`
+/// * the same number in angle brackets, as it appears in a message: `<134>`
+/// * a facility and severity separated by a period, each given either by name
+///   or by numeric code: `local0.info`, `16.6`, or `local0.6`
+/// * a severity alone, which uses the user facility: `info`
+///
+/// Names are matched ignoring case, and may be any of the aliases that
+/// [`Facility`] and [`Severity`] accept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Prival(u8);
+
+impl Prival {
+    pub const MAX: u8 = 191;
+
+    pub fn new(facility: Facility, severity: Severity) -> Self {
+        Prival(facility.code() * 8 + severity.code())
+    }
+
+    pub fn facility(self) -> Facility {
+        Facility::try_from(self.0 >> 3).unwrap()
+    }
+
+    pub fn severity(self) -> Severity {
+        Severity::try_from(self.0 & 0x07).unwrap()
+    }
+
+    pub fn value(self) -> u8 {
+        self.0
+    }
+}
+
+/// Formats the prival as `facility.severity` using canonical names.
+impl fmt::Display for Prival {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.facility(), self.severity())
+    }
+}
+
+impl FromStr for Prival {
+    type Err = StumplessError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        if trimmed.is_empty() {
+            return Err(invalid_priority(s, "no facility or severity given"));
+        }
+
+        if let Some(bracketed) = trimmed.strip_prefix('<') {
+            return match bracketed.strip_suffix('>') {
+                Some(value) => prival_from_number(value, s),
+                None => Err(invalid_priority(s, "missing closing '>'")),
+            };
+        }
+
+        if let Some((facility, severity)) = trimmed.split_once('.') {
+            return Ok(Prival::new(
+                parse_facility(facility, s)?,
+                parse_severity(severity, s)?,
+            ));
+        }
+
+        if is_numeric(trimmed) {
+            return prival_from_number(trimmed, s);
+        }
+
+        Ok(Prival::new(Facility::User, parse_severity(trimmed, s)?))
+    }
+}
+
+impl TryFrom<u8> for Prival {
+    type Error = StumplessError;
+
+    fn try_from(value: u8) -> Result<Self, StumplessError> {
+        if value > Prival::MAX {
+            Err(StumplessError::new(
+                ErrorKind::InvalidPrival,
+                &format!("prival {} is greater than {}", value, Prival::MAX),
+            ))
+        } else {
+            Ok(Prival(value))
+        }
+    }
+}
+
+impl TryFrom<i32> for Prival {
+    type Error = StumplessError;
+
+    fn try_from(value: i32) -> Result<Self, StumplessError> {
+        match u8::try_from(value) {
+            Ok(value) => Prival::try_from(value),
+            Err(_) => Err(StumplessError::new(
+                ErrorKind::InvalidPrival,
+                &format!("prival {} is not between 0 and {}", value, Prival::MAX),
+            )),
+        }
+    }
+}
+
+impl From<Prival> for u8 {
+    fn from(prival: Prival) -> Self {
+        prival.0
+    }
+}
+
+impl From<Prival> for i32 {
+    fn from(prival: Prival) -> Self {
+        prival.0.into()
+    }
+}
+
+impl From<(Facility, Severity)> for Prival {
+    fn from((facility, severity): (Facility, Severity)) -> Self {
+        Prival::new(facility, severity)
+    }
+}
+
+fn invalid_priority(priority: &str, reason: &str) -> StumplessError {
+    StumplessError::new(
+        ErrorKind::InvalidPrival,
+        &format!("invalid priority '{}': {}", priority, reason),
+    )
+}
+
+// only digits are allowed, as `u8::from_str` also accepts a leading sign
+fn is_numeric(token: &str) -> bool {
+    !token.is_empty() && token.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn numeric_code(token: &str) -> Option<u8> {
+    if is_numeric(token) {
+        token.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn prival_from_number(token: &str, priority: &str) -> Result<Prival, StumplessError> {
+    match numeric_code(token) {
+        Some(value) if value <= Prival::MAX => Ok(Prival(value)),
+        _ => Err(invalid_priority(
+            priority,
+            &format!("'{}' is not a number from 0 to {}", token, Prival::MAX),
+        )),
+    }
+}
+
+// a facility given by name or by numeric code
+fn parse_facility(token: &str, priority: &str) -> Result<Facility, StumplessError> {
+    let parsed = match numeric_code(token) {
+        Some(code) => Facility::try_from(code),
+        None => token.parse(),
+    };
+
+    parsed.map_err(|_| {
+        StumplessError::new(
+            ErrorKind::InvalidFacility,
+            &format!(
+                "invalid priority '{}': unknown facility '{}'",
+                priority, token
+            ),
+        )
+    })
+}
+
+// a severity given by name or by numeric code
+fn parse_severity(token: &str, priority: &str) -> Result<Severity, StumplessError> {
+    let parsed = match numeric_code(token) {
+        Some(code) => Severity::try_from(code),
+        None => token.parse(),
+    };
+
+    parsed.map_err(|_| {
+        StumplessError::new(
+            ErrorKind::InvalidSeverity,
+            &format!(
+                "invalid priority '{}': unknown severity '{}'",
+                priority, token
+            ),
+        )
+    })
+}
@@ -0,0 +1,100 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{ErrorKind, StumplessError};
+
+/// The IP version used to reach a server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NetworkProtocol {
+    Ipv4,
+    Ipv6,
+}
+
+/// The transport protocol used to send entries to a server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransportProtocol {
+    Tcp,
+    Udp,
+}
+
+/// How messages are separated from each other on a TCP connection, as
+/// described in RFC 6587.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Framing {
+    /// Each message is sent after its length in bytes and a space.
+    #[default]
+    OctetCounting,
+    /// Each message is followed by a newline. Newlines inside of a message are
+    /// escaped as `#012`, so that the receiver does not split it.
+    NonTransparent,
+}
+
+impl Framing {
+    pub fn name(self) -> &'static str {
+        match self {
+            Framing::OctetCounting => "octet-counting",
+            Framing::NonTransparent => "non-transparent",
+        }
+    }
+}
+
+impl FromStr for Framing {
+    type Err = StumplessError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "octet-counting" => Ok(Framing::OctetCounting),
+            "non-transparent" => Ok(Framing::NonTransparent),
+            _ => Err(StumplessError::new(
+                ErrorKind::InvalidFraming,
+                &format!("invalid framing name: '{}'", s),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Framing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// splits a server address into its host and port, if it has one, or returns
+// None if it is malformed
+pub(crate) fn split_address(address: &str) -> Option<(&str, Option<&str>)> {
+    let (host, port) = if let Some(bracketed) = address.strip_prefix('[') {
+        let (host, rest) = bracketed.split_once(']')?;
+        let port = match rest {
+            "" => None,
+            _ => Some(rest.strip_prefix(':')?),
+        };
+        (host, port)
+    } else {
+        match address.split_once(':') {
+            Some((host, port)) if !port.contains(':') => (host, Some(port)),
+            _ => (address, None),
+        }
+    };
+
+    if host.is_empty() || port == Some("") {
+        None
+    } else {
+        Some((host, port))
+    }
+}
@@ -16,6 +16,21 @@
 
 use stumpless_sys::*;
 
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{ErrorKind, StumplessError};
+
+/// The severity of a log entry, as defined in RFC 5424.
+///
+/// Severities parse from their names and aliases ignoring case, format back to
+/// their canonical names, and can be converted to and from their numeric code.
+///
+/// Severities are ordered by how severe they are, so that
+/// `Severity::Error > Severity::Warning`. This is the reverse of the order of
+/// their numeric codes, where 0 is the most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Severity {
     Emergency = stumpless_severity_STUMPLESS_SEVERITY_EMERG as isize,
     Alert = stumpless_severity_STUMPLESS_SEVERITY_ALERT as isize,
@@ -26,3 +41,101 @@ pub enum Severity {
     Info = stumpless_severity_STUMPLESS_SEVERITY_INFO as isize,
     Debug = stumpless_severity_STUMPLESS_SEVERITY_DEBUG as isize,
 }
+
+impl Severity {
+    /// The name that this severity is displayed as.
+    pub fn name(self) -> &'static str {
+        match self {
+            Severity::Emergency => "emerg",
+            Severity::Alert => "alert",
+            Severity::Critical => "crit",
+            Severity::Error => "err",
+            Severity::Warning => "warning",
+            Severity::Notice => "notice",
+            Severity::Info => "info",
+            Severity::Debug => "debug",
+        }
+    }
+
+    /// The numeric code of the severity, as used in a prival.
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// The bit that enables this severity in a target mask.
+    pub fn mask(self) -> i32 {
+        1 << self.code()
+    }
+
+    /// A target mask enabling this severity and all of those more severe
+    /// than it.
+    pub fn mask_upto(self) -> i32 {
+        (1 << (self.code() + 1)) - 1
+    }
+}
+
+impl Ord for Severity {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.code().cmp(&self.code())
+    }
+}
+
+impl PartialOrd for Severity {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Severity {
+    type Err = StumplessError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "emerg" | "panic" => Ok(Severity::Emergency),
+            "alert" => Ok(Severity::Alert),
+            "crit" => Ok(Severity::Critical),
+            "err" | "error" => Ok(Severity::Error),
+            "warning" | "warn" => Ok(Severity::Warning),
+            "notice" => Ok(Severity::Notice),
+            "info" => Ok(Severity::Info),
+            "debug" => Ok(Severity::Debug),
+            _ => Err(StumplessError::new(
+                ErrorKind::InvalidSeverity,
+                &format!("invalid severity name: '{}'", s),
+            )),
+        }
+    }
+}
+
+impl TryFrom<u8> for Severity {
+    type Error = StumplessError;
+
+    fn try_from(code: u8) -> Result<Self, StumplessError> {
+        match code {
+            0 => Ok(Severity::Emergency),
+            1 => Ok(Severity::Alert),
+            2 => Ok(Severity::Critical),
+            3 => Ok(Severity::Error),
+            4 => Ok(Severity::Warning),
+            5 => Ok(Severity::Notice),
+            6 => Ok(Severity::Info),
+            7 => Ok(Severity::Debug),
+            _ => Err(StumplessError::new(
+                ErrorKind::InvalidSeverity,
+                &format!("invalid severity code: {}", code),
+            )),
+        }
+    }
+}
+
+impl From<Severity> for u8 {
+    fn from(severity: Severity) -> Self {
+        severity.code()
+    }
+}
@@ -16,10 +16,10 @@
 
 use stumpless_sys::*;
 
-use std::error::Error;
 use std::ffi::CString;
 
-use crate::error::last_error;
+use crate::error::{last_error, StumplessError};
+use crate::filter;
 use crate::Target;
 
 pub struct SocketTarget {
@@ -27,16 +27,13 @@ pub struct SocketTarget {
 }
 
 impl SocketTarget {
-    pub fn new(socket_name: &str) -> Result<Self, Box<dyn Error>> {
+    pub fn new(socket_name: &str) -> Result<Self, StumplessError> {
         let c_socket_name = CString::new(socket_name)?;
         let socket_target =
             unsafe { stumpless_open_socket_target(c_socket_name.as_ptr(), std::ptr::null()) };
 
         if socket_target.is_null() {
-            match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(Box::new(err)),
-            }
+            Err(last_error())
         } else {
             Ok(SocketTarget {
                 target: socket_target,
@@ -45,6 +42,7 @@ impl SocketTarget {
     }
 }
 
+unsafe impl Send for SocketTarget {}
 unsafe impl Sync for SocketTarget {}
 
 impl Target for SocketTarget {
@@ -55,6 +53,7 @@ impl Target for SocketTarget {
 
 impl Drop for SocketTarget {
     fn drop(&mut self) {
+        filter::remove_filter(self.target);
         unsafe {
             stumpless_close_socket_target(self.target);
         }
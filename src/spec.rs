@@ -0,0 +1,323 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{ErrorKind, StumplessError};
+use crate::file::FileTarget;
+use crate::protocol::{split_address, Framing, NetworkProtocol, TransportProtocol};
+use crate::stream::StreamTarget;
+use crate::target::{DefaultTarget, Target};
+
+#[cfg(feature = "journald")]
+use crate::journald::JournaldTarget;
+#[cfg(feature = "network")]
+use crate::network::NetworkTarget;
+#[cfg(feature = "socket")]
+use crate::socket::SocketTarget;
+#[cfg(feature = "sqlite")]
+use crate::sqlite3::Sqlite3Target;
+#[cfg(feature = "wel")]
+use crate::wel::WelTarget;
+
+/// A description of a target that can be opened later, written as a URI.
+///
+/// | URI                              | Target                            |
+/// |----------------------------------|-----------------------------------|
+/// | `stdout:`, `stderr:`             | [`StreamTarget`]                  |
+/// | `file:///var/log/app.log`        | [`FileTarget`]                    |
+/// | `udp://host:514`, `tcp://host`   | `NetworkTarget` using IPv4        |
+/// | `udp6://[::1]:514`, `tcp6://::1` | `NetworkTarget` using IPv6        |
+/// | `unix:///dev/log`                | `SocketTarget`                    |
+/// | `journald://`                    | `JournaldTarget`                  |
+/// | `sqlite:///var/log/app.db`       | `Sqlite3Target`                   |
+/// | `wel://Application`              | `WelTarget`                       |
+/// | `default:`                       | [`DefaultTarget`]                 |
+///
+/// Network schemes may also be written as `udp4` and `tcp4`. Plain `udp` and
+/// `tcp` use IPv4 unless the host is an IPv6 address in brackets. TCP targets
+/// use octet counting unless another [`Framing`] is given, as in
+/// `tcp://host:601?framing=non-transparent`. File paths
+/// may also be relative, as in `file:app.log`.
+///
+/// Any URI can be parsed regardless of the features this crate was built with,
+/// but opening a target whose feature is not enabled fails.
+///
+/// ```no_run
+/// # use stumpless::{Target, TargetSpec};
+/// let spec: TargetSpec = "tcp6://[::1]:601".parse().unwrap();
+/// let target = spec.open().unwrap();
+/// target.add_message("sent to port 601").unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TargetSpec {
+    Default,
+    Stdout,
+    Stderr,
+    File(String),
+    Network {
+        network: NetworkProtocol,
+        transport: TransportProtocol,
+        host: String,
+        port: Option<String>,
+        framing: Option<Framing>,
+    },
+    Socket(String),
+    Journald,
+    Sqlite(String),
+    WindowsEventLog(String),
+}
+
+fn invalid_spec(spec: &str, reason: &str) -> StumplessError {
+    StumplessError::new(
+        ErrorKind::InvalidTargetSpec,
+        &format!("invalid target '{}': {}", spec, reason),
+    )
+}
+
+// unused when every target feature is enabled
+#[allow(dead_code)]
+fn not_enabled(feature: &str) -> StumplessError {
+    StumplessError::new(
+        ErrorKind::TargetUnsupported,
+        &format!("the {} feature is not enabled", feature),
+    )
+}
+
+impl TargetSpec {
+    /// Opens the target that the spec describes.
+    ///
+    /// Network targets without a port use port 514, and TCP targets without a
+    /// framing use octet counting. SQLite databases have the
+    /// default logs table created in them if it does not exist yet.
+    pub fn open(&self) -> Result<Box<dyn Target + Send>, StumplessError> {
+        match self {
+            TargetSpec::Default => Ok(Box::new(DefaultTarget::get_default_target()?)),
+            TargetSpec::Stdout => Ok(Box::new(StreamTarget::stdout("stdout")?)),
+            TargetSpec::Stderr => Ok(Box::new(StreamTarget::stderr("stderr")?)),
+            TargetSpec::File(path) => Ok(Box::new(FileTarget::new(path)?)),
+            #[cfg(feature = "network")]
+            TargetSpec::Network {
+                network,
+                transport,
+                host,
+                port,
+                framing,
+            } => {
+                // the host is passed on its own so that IPv6 addresses are not
+                // taken to include a port
+                let target = NetworkTarget::builder(&format!("[{}]", host))
+                    .network(*network)
+                    .transport(*transport)
+                    .port(port.as_deref().unwrap_or(NetworkTarget::DEFAULT_PORT))
+                    .framing(framing.unwrap_or_default())
+                    .build()?;
+                Ok(Box::new(target))
+            }
+            #[cfg(not(feature = "network"))]
+            TargetSpec::Network { .. } => Err(not_enabled("network")),
+            #[cfg(feature = "socket")]
+            TargetSpec::Socket(path) => Ok(Box::new(SocketTarget::new(path)?)),
+            #[cfg(not(feature = "socket"))]
+            TargetSpec::Socket(_) => Err(not_enabled("socket")),
+            #[cfg(feature = "journald")]
+            TargetSpec::Journald => Ok(Box::new(JournaldTarget::new("journald")?)),
+            #[cfg(not(feature = "journald"))]
+            TargetSpec::Journald => Err(not_enabled("journald")),
+            #[cfg(feature = "sqlite")]
+            TargetSpec::Sqlite(path) => {
+                let target = Sqlite3Target::new(path)?;
+                target.create_default_table()?;
+                Ok(Box::new(target))
+            }
+            #[cfg(not(feature = "sqlite"))]
+            TargetSpec::Sqlite(_) => Err(not_enabled("sqlite")),
+            #[cfg(feature = "wel")]
+            TargetSpec::WindowsEventLog(log_name) => Ok(Box::new(WelTarget::new(log_name)?)),
+            #[cfg(not(feature = "wel"))]
+            TargetSpec::WindowsEventLog(_) => Err(not_enabled("wel")),
+        }
+    }
+}
+
+// the path of a file or socket, either as an absolute file:/// style path or
+// directly after the scheme
+fn parse_path<'a>(spec: &str, rest: &'a str) -> Result<&'a str, StumplessError> {
+    let path = match rest.strip_prefix("//") {
+        Some(authority_and_path) => {
+            let path_start = authority_and_path
+                .find('/')
+                .ok_or_else(|| invalid_spec(spec, "the path must be absolute"))?;
+            match &authority_and_path[..path_start] {
+                "" | "localhost" => &authority_and_path[path_start..],
+                _ => return Err(invalid_spec(spec, "only local paths are supported")),
+            }
+        }
+        None => rest,
+    };
+
+    if path.is_empty() {
+        Err(invalid_spec(spec, "the path is empty"))
+    } else {
+        Ok(path)
+    }
+}
+
+// the part after the scheme for targets that don't take any
+fn parse_empty(spec: &str, rest: &str) -> Result<(), StumplessError> {
+    match rest {
+        "" | "//" => Ok(()),
+        _ => Err(invalid_spec(spec, "this kind of target takes no address")),
+    }
+}
+
+fn parse_network(
+    spec: &str,
+    rest: &str,
+    network: Option<NetworkProtocol>,
+    transport: TransportProtocol,
+) -> Result<TargetSpec, StumplessError> {
+    let authority = rest
+        .strip_prefix("//")
+        .ok_or_else(|| invalid_spec(spec, "the server must follow '//'"))?;
+    let (authority, query) = match authority.split_once('?') {
+        Some((authority, query)) => (authority, Some(query)),
+        None => (authority, None),
+    };
+    let authority = authority.strip_suffix('/').unwrap_or(authority);
+
+    if authority.contains('/') {
+        return Err(invalid_spec(spec, "network targets do not take a path"));
+    }
+
+    let (host, port) = split_address(authority)
+        .ok_or_else(|| invalid_spec(spec, "expected host, host:port, or [address]:port"))?;
+    let network = network.unwrap_or(if authority.starts_with('[') {
+        NetworkProtocol::Ipv6
+    } else {
+        NetworkProtocol::Ipv4
+    });
+
+    let framing = match query {
+        None => None,
+        Some(_) if transport == TransportProtocol::Udp => {
+            return Err(invalid_spec(spec, "framing only applies to tcp"));
+        }
+        Some(query) => {
+            let framing = query
+                .strip_prefix("framing=")
+                .ok_or_else(|| invalid_spec(spec, "the only option is framing"))?;
+            Some(
+                framing
+                    .parse()
+                    .map_err(|_| invalid_spec(spec, "unknown framing"))?,
+            )
+        }
+    };
+
+    Ok(TargetSpec::Network {
+        network,
+        transport,
+        host: host.to_string(),
+        port: port.map(str::to_string),
+        framing,
+    })
+}
+
+impl FromStr for TargetSpec {
+    type Err = StumplessError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        use NetworkProtocol::{Ipv4, Ipv6};
+
+        let (scheme, rest) = spec
+            .split_once(':')
+            .ok_or_else(|| invalid_spec(spec, "expected a scheme such as file: or udp:"))?;
+
+        match scheme.to_ascii_lowercase().as_str() {
+            "default" => parse_empty(spec, rest).map(|()| TargetSpec::Default),
+            "stdout" => parse_empty(spec, rest).map(|()| TargetSpec::Stdout),
+            "stderr" => parse_empty(spec, rest).map(|()| TargetSpec::Stderr),
+            "journald" => parse_empty(spec, rest).map(|()| TargetSpec::Journald),
+            "file" => Ok(TargetSpec::File(parse_path(spec, rest)?.to_string())),
+            "unix" => Ok(TargetSpec::Socket(parse_path(spec, rest)?.to_string())),
+            "sqlite" => Ok(TargetSpec::Sqlite(parse_path(spec, rest)?.to_string())),
+            "wel" => match rest.strip_prefix("//") {
+                Some("") => Ok(TargetSpec::WindowsEventLog("Stumpless".to_string())),
+                Some(log_name) if !log_name.contains('/') => {
+                    Ok(TargetSpec::WindowsEventLog(log_name.to_string()))
+                }
+                _ => Err(invalid_spec(spec, "expected wel://<log name>")),
+            },
+            "tcp" => parse_network(spec, rest, None, TransportProtocol::Tcp),
+            "tcp4" => parse_network(spec, rest, Some(Ipv4), TransportProtocol::Tcp),
+            "tcp6" => parse_network(spec, rest, Some(Ipv6), TransportProtocol::Tcp),
+            "udp" => parse_network(spec, rest, None, TransportProtocol::Udp),
+            "udp4" => parse_network(spec, rest, Some(Ipv4), TransportProtocol::Udp),
+            "udp6" => parse_network(spec, rest, Some(Ipv6), TransportProtocol::Udp),
+            _ => Err(invalid_spec(spec, "unknown scheme")),
+        }
+    }
+}
+
+impl fmt::Display for TargetSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TargetSpec::Default => write!(f, "default:"),
+            TargetSpec::Stdout => write!(f, "stdout:"),
+            TargetSpec::Stderr => write!(f, "stderr:"),
+            TargetSpec::File(path) => write_path(f, "file", path),
+            TargetSpec::Socket(path) => write_path(f, "unix", path),
+            TargetSpec::Sqlite(path) => write_path(f, "sqlite", path),
+            TargetSpec::Journald => write!(f, "journald://"),
+            TargetSpec::WindowsEventLog(log_name) => write!(f, "wel://{}", log_name),
+            TargetSpec::Network {
+                network,
+                transport,
+                host,
+                port,
+                framing,
+            } => {
+                let scheme = match (transport, network) {
+                    (TransportProtocol::Tcp, NetworkProtocol::Ipv4) => "tcp4",
+                    (TransportProtocol::Tcp, NetworkProtocol::Ipv6) => "tcp6",
+                    (TransportProtocol::Udp, NetworkProtocol::Ipv4) => "udp4",
+                    (TransportProtocol::Udp, NetworkProtocol::Ipv6) => "udp6",
+                };
+
+                match (host.contains(':'), port) {
+                    (true, Some(port)) => write!(f, "{}://[{}]:{}", scheme, host, port)?,
+                    (false, Some(port)) => write!(f, "{}://{}:{}", scheme, host, port)?,
+                    (_, None) => write!(f, "{}://{}", scheme, host)?,
+                }
+
+                match framing {
+                    Some(framing) => write!(f, "?framing={}", framing),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+fn write_path(f: &mut fmt::Formatter<'_>, scheme: &str, path: &str) -> fmt::Result {
+    if path.starts_with('/') {
+        write!(f, "{}://{}", scheme, path)
+    } else {
+        write!(f, "{}:{}", scheme, path)
+    }
+}
@@ -0,0 +1,190 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use stumpless_sys::stumpless_target;
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::SystemTime;
+
+use rusqlite::types::ToSql;
+use rusqlite::Connection;
+
+use crate::entry::{rfc5424_timestamp, Entry};
+use crate::error::{ErrorKind, StumplessError};
+use crate::function::FunctionTarget;
+use crate::prival::Prival;
+use crate::severity::Severity;
+use crate::Target;
+
+const DEFAULT_TABLE_SQL: &str = "CREATE TABLE IF NOT EXISTS logs ( \
+    log_id INTEGER PRIMARY KEY, \
+    prival INTEGER NOT NULL, \
+    version INTEGER NOT NULL, \
+    timestamp TEXT, \
+    hostname TEXT, \
+    app_name TEXT, \
+    procid TEXT, \
+    msgid TEXT, \
+    structured_data TEXT, \
+    message TEXT )";
+
+fn sqlite_error(err: rusqlite::Error) -> StumplessError {
+    match &err {
+        rusqlite::Error::SqliteFailure(failure, _) => StumplessError::with_code(
+            ErrorKind::Sqlite3Failure,
+            &err.to_string(),
+            failure.extended_code,
+            "sqlite3 result code",
+        ),
+        _ => StumplessError::new(ErrorKind::Sqlite3Failure, &err.to_string()),
+    }
+}
+
+struct Database {
+    connection: Connection,
+    insert_sql: String,
+}
+
+impl Database {
+    fn insert(&self, entry: &Entry) -> Result<(), StumplessError> {
+        let prival = Prival::try_from(entry.prival())?;
+        let timestamp = rfc5424_timestamp(entry.timestamp().unwrap_or_else(SystemTime::now));
+        let message = entry.message()?.unwrap_or_default();
+        let fields: [(&str, &dyn ToSql); 11] = [
+            ("$prival", &prival.value()),
+            ("$facility", &prival.facility().code()),
+            ("$severity", &prival.severity().code()),
+            ("$version", &1),
+            ("$timestamp", &timestamp),
+            ("$hostname", &entry.hostname()?),
+            ("$app_name", &entry.app_name()?),
+            ("$procid", &entry.explicit_procid()?),
+            ("$msgid", &entry.msgid()?),
+            ("$structured_data", &entry.structured_data()?),
+            ("$message", &message),
+        ];
+
+        let mut insert = self
+            .connection
+            .prepare_cached(&self.insert_sql)
+            .map_err(sqlite_error)?;
+
+        // only the fields used by the statement are bound
+        for (name, value) in fields {
+            if let Some(index) = insert.parameter_index(name).map_err(sqlite_error)? {
+                insert
+                    .raw_bind_parameter(index, value)
+                    .map_err(sqlite_error)?;
+            }
+        }
+
+        insert.raw_execute().map_err(sqlite_error)?;
+        Ok(())
+    }
+}
+
+/// A target that inserts entries into a SQLite3 database.
+///
+/// By default each entry is inserted as a row in a table named `logs`, which
+/// can be made with [`Sqlite3Target::create_default_table`]. A different insert
+/// statement can be provided to log into some other table, using any of these
+/// named parameters for the fields of the entry: `$prival`, `$facility`,
+/// `$severity`, `$version`, `$timestamp`, `$hostname`, `$app_name`, `$procid`,
+/// `$msgid`, `$structured_data`, and `$message`. The facility and severity are
+/// given as their numeric codes, and the other fields as they would appear in
+/// a formatted message.
+///
+/// This target uses [`rusqlite`](https://docs.rs/rusqlite), which links
+/// against the system SQLite3 library.
+pub struct Sqlite3Target {
+    function_target: FunctionTarget,
+    database: Arc<Mutex<Database>>,
+}
+
+impl Sqlite3Target {
+    pub const DEFAULT_INSERT_SQL: &'static str = "INSERT INTO logs ( prival, version, \
+        timestamp, hostname, app_name, procid, msgid, structured_data, message ) \
+        VALUES ( $prival, 1, $timestamp, $hostname, $app_name, $procid, $msgid, \
+        $structured_data, $message )";
+
+    /// Opens the database with the given filename, creating it if it does not
+    /// exist yet.
+    pub fn new(db_filename: &str) -> Result<Self, StumplessError> {
+        let connection = Connection::open(db_filename).map_err(sqlite_error)?;
+        let database = Arc::new(Mutex::new(Database {
+            connection,
+            insert_sql: Self::DEFAULT_INSERT_SQL.to_string(),
+        }));
+
+        let target_database = Arc::clone(&database);
+        let function_target = FunctionTarget::new(db_filename, move |entry| {
+            lock(&target_database).insert(entry)
+        })?;
+
+        Ok(Sqlite3Target {
+            function_target,
+            database,
+        })
+    }
+
+    /// Creates the `logs` table used by the default insert statement, if it
+    /// does not already exist.
+    pub fn create_default_table(&self) -> Result<&Self, StumplessError> {
+        lock(&self.database)
+            .connection
+            .execute_batch(DEFAULT_TABLE_SQL)
+            .map_err(sqlite_error)?;
+
+        Ok(self)
+    }
+
+    /// Sets the statement used to insert each entry into the database, which
+    /// fails if the statement is not valid for the database.
+    pub fn set_insert_sql(&self, sql: &str) -> Result<&Self, StumplessError> {
+        let mut database = lock(&self.database);
+        database
+            .connection
+            .prepare_cached(sql)
+            .map_err(sqlite_error)?;
+        database.insert_sql = sql.to_string();
+
+        Ok(self)
+    }
+}
+
+// a panic while inserting cannot leave the database in a bad state, so the
+// lock is still used if it was poisoned
+fn lock(database: &Mutex<Database>) -> MutexGuard<'_, Database> {
+    database.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl Target for Sqlite3Target {
+    fn get_pointer(&self) -> *mut stumpless_target {
+        self.function_target.get_pointer()
+    }
+
+    fn add_entry(&self, entry: &Entry) -> Result<u32, StumplessError> {
+        self.function_target.add_entry(entry)
+    }
+
+    fn add_message(&self, message: &str) -> Result<u32, StumplessError> {
+        self.function_target.add_message(message)
+    }
+
+    fn log(&self, severity: Severity, message: &str) -> Result<u32, StumplessError> {
+        self.function_target.log(severity, message)
+    }
+}
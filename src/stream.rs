@@ -14,64 +14,65 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use stumpless_sys::*;
+use stumpless_sys::stumpless_target;
 
-use std::error::Error;
-use std::ffi::CString;
+use std::io::{self, Write};
 
-use crate::error::last_error;
+use crate::error::StumplessError;
+use crate::function::FunctionTarget;
 use crate::Target;
 
+/// A target that writes entries to stdout or stderr.
+///
+/// The name of a stream target only identifies the target, so the app name of
+/// messages logged with [`Target::add_message`] is set with
+/// [`Target::set_default_app_name`].
 pub struct StreamTarget {
-    target: *mut stumpless_target,
+    target: FunctionTarget,
+    stream: Stream,
 }
 
-impl StreamTarget {
-    pub fn stderr(filename: &str) -> Result<Self, Box<dyn Error>> {
-        let c_filename = CString::new(filename)?;
-        let stream_target = unsafe { stumpless_open_stderr_target(c_filename.as_ptr()) };
+#[derive(Clone, Copy)]
+enum Stream {
+    Stdout,
+    Stderr,
+}
 
-        if stream_target.is_null() {
-            match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(Box::new(err)),
-            }
-        } else {
-            Ok(StreamTarget {
-                target: stream_target,
-            })
+impl Stream {
+    fn writer(self) -> Box<dyn Write> {
+        match self {
+            Stream::Stdout => Box::new(io::stdout().lock()),
+            Stream::Stderr => Box::new(io::stderr().lock()),
         }
     }
+}
 
-    pub fn stdout(filename: &str) -> Result<Self, Box<dyn Error>> {
-        let c_filename = CString::new(filename)?;
-        let stream_target = unsafe { stumpless_open_stdout_target(c_filename.as_ptr()) };
+impl StreamTarget {
+    pub fn stderr(name: &str) -> Result<Self, StumplessError> {
+        StreamTarget::open(name, Stream::Stderr)
+    }
 
-        if stream_target.is_null() {
-            match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(Box::new(err)),
-            }
-        } else {
-            Ok(StreamTarget {
-                target: stream_target,
-            })
-        }
+    pub fn stdout(name: &str) -> Result<Self, StumplessError> {
+        StreamTarget::open(name, Stream::Stdout)
     }
-}
 
-unsafe impl Sync for StreamTarget {}
+    fn open(name: &str, stream: Stream) -> Result<Self, StumplessError> {
+        let target = FunctionTarget::formatted(name, move |message| {
+            let mut writer = stream.writer();
+            writer.write_all(message)?;
+            Ok(writer.write_all(b"\n")?)
+        })?;
+
+        Ok(StreamTarget { target, stream })
+    }
+}
 
 impl Target for StreamTarget {
     fn get_pointer(&self) -> *mut stumpless_target {
-        self.target
+        self.target.get_pointer()
     }
-}
 
-impl Drop for StreamTarget {
-    fn drop(&mut self) {
-        unsafe {
-            stumpless_close_stream_target(self.target);
-        }
+    fn flush(&self) -> Result<(), StumplessError> {
+        Ok(self.stream.writer().flush()?)
     }
 }
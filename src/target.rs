@@ -15,72 +15,268 @@
 // limitations under the License.
 
 use crate::entry::Entry;
-use crate::error::{last_error, StumplessError};
-use std::error::Error;
+use crate::error::{last_error, ErrorKind, StumplessError};
+use crate::facility::Facility;
+use crate::filter;
+use crate::function;
+use crate::level::level_enabled;
+use crate::memory::required_string;
+use crate::prival::Prival;
+use crate::severity::Severity;
 use std::ffi::CString;
-use stumpless_sys::{
-    stumpless_add_entry, stumpless_add_message_str, stumpless_get_default_target,
-    stumpless_open_target, stumpless_target,
-};
+use std::sync::Arc;
+use stumpless_sys::*;
+
+/// The options that change how a target logs entries, named after the
+/// matching options of `openlog`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TargetOption {
+    /// Includes the process id in the procid of each entry.
+    Pid = STUMPLESS_OPTION_PID as isize,
+    /// Writes entries to the console stream if logging to the target fails.
+    Cons = STUMPLESS_OPTION_CONS as isize,
+    /// Opens the connection to the target immediately.
+    Ndelay = STUMPLESS_OPTION_NDELAY as isize,
+    /// Waits until the first entry is logged to open the connection.
+    Odelay = STUMPLESS_OPTION_ODELAY as isize,
+    /// Writes entries to stderr as well as the target.
+    Perror = STUMPLESS_OPTION_PERROR as isize,
+}
+
+// setters return the target on success and null on failure
+fn set_result(target: *mut stumpless_target) -> Result<(), StumplessError> {
+    if target.is_null() {
+        Err(last_error())
+    } else {
+        Ok(())
+    }
+}
 
 pub trait Target: Sync {
     fn get_pointer(&self) -> *mut stumpless_target;
 
     fn add_entry(&self, entry: &Entry) -> Result<u32, StumplessError> {
-        let add_result = unsafe { stumpless_add_entry(self.get_pointer(), entry.entry) };
-
-        if add_result >= 0 {
-            Ok(add_result.try_into().unwrap())
-        } else {
-            match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(err),
-            }
-        }
+        function::dispatch(|| {
+            entry.adding(|| unsafe { stumpless_add_entry(self.get_pointer(), entry.entry) })
+        })
     }
 
-    fn add_message(&self, message: &str) -> Result<u32, Box<dyn Error>> {
+    fn add_message(&self, message: &str) -> Result<u32, StumplessError> {
         let c_message = CString::new(message)?;
 
-        let add_result =
-            unsafe { stumpless_add_message_str(self.get_pointer(), c_message.as_ptr()) };
-
-        if add_result >= 0 {
-            Ok(add_result.try_into().unwrap())
-        } else {
-            match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(Box::new(err)),
-            }
-        }
+        function::dispatch(|| unsafe {
+            stumpless_add_message_str(self.get_pointer(), c_message.as_ptr())
+        })
     }
 
     fn open(&self) -> Result<(), StumplessError> {
         let open_result = unsafe { stumpless_open_target(self.get_pointer()) };
         if open_result.is_null() {
-            match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(err),
-            }
+            Err(last_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Writes out any messages that the target is holding in a buffer. Only
+    /// file and stream targets buffer messages, so this does nothing for the
+    /// other target types.
+    fn flush(&self) -> Result<(), StumplessError> {
+        Ok(())
+    }
+
+    /// Reopens whatever the target logs to, for example after a log file has
+    /// been rotated or a server has restarted.
+    ///
+    /// File targets open their file again by name, and network targets make a
+    /// new connection to their server. If a network target cannot reconnect,
+    /// it cannot log anything until it is reopened successfully. Other target
+    /// types have nothing to reopen, so this does nothing for them.
+    fn reopen(&self) -> Result<(), StumplessError> {
+        let target_type = unsafe { (*self.get_pointer()).type_ };
+
+        if target_type == stumpless_target_type_STUMPLESS_NETWORK_TARGET {
+            self.open()
         } else {
             Ok(())
         }
     }
+
+    /// Closes the target, returning an error if any of the messages buffered
+    /// by it could not be written out.
+    ///
+    /// Dropping a target also closes it, but without reporting any errors.
+    fn close(self) -> Result<(), StumplessError>
+    where
+        Self: Sized,
+    {
+        let flush_result = self.flush();
+        drop(self);
+        flush_result
+    }
+
+    /// Sets which severities are logged to this target. Entries with a
+    /// severity that is not in the mask are dropped without an error.
+    ///
+    /// Masks are built from [`Severity::mask`] and [`Severity::mask_upto`],
+    /// for example `Severity::Warning.mask_upto()` to only log warnings and
+    /// anything more severe.
+    ///
+    /// [`Severity::mask`]: crate::Severity::mask
+    /// [`Severity::mask_upto`]: crate::Severity::mask_upto
+    fn set_mask(&self, mask: i32) -> Result<(), StumplessError> {
+        set_result(unsafe { stumpless_set_target_mask(self.get_pointer(), mask) })
+    }
+
+    fn get_mask(&self) -> i32 {
+        unsafe { stumpless_get_target_mask(self.get_pointer()) }
+    }
+
+    /// Sets a closure that decides whether each entry is logged to this
+    /// target, replacing any filter that was already set.
+    ///
+    /// The filter is only called for entries that pass the target's mask. If
+    /// it panics then the entry is dropped.
+    fn set_filter<F>(&self, filter: F) -> Result<(), StumplessError>
+    where
+        Self: Sized,
+        F: Fn(&Entry) -> bool + Send + Sync + 'static,
+    {
+        filter::set_filter(self.get_pointer(), Arc::new(filter))
+    }
+
+    /// Removes the filter closure of this target, so that only its mask is
+    /// used to decide which entries are logged.
+    fn clear_filter(&self) -> Result<(), StumplessError> {
+        filter::clear_filter(self.get_pointer())
+    }
+
+    /// Sets the app name used for messages logged with [`Target::add_message`].
+    fn set_default_app_name(&self, app_name: &str) -> Result<(), StumplessError> {
+        let c_app_name = CString::new(app_name)?;
+        set_result(unsafe {
+            stumpless_set_target_default_app_name(self.get_pointer(), c_app_name.as_ptr())
+        })
+    }
+
+    fn get_default_app_name(&self) -> Result<String, StumplessError> {
+        required_string(unsafe { stumpless_get_target_default_app_name(self.get_pointer()) })
+    }
+
+    /// Sets the msgid used for messages logged with [`Target::add_message`].
+    fn set_default_msgid(&self, msgid: &str) -> Result<(), StumplessError> {
+        let c_msgid = CString::new(msgid)?;
+        set_result(unsafe {
+            stumpless_set_target_default_msgid(self.get_pointer(), c_msgid.as_ptr())
+        })
+    }
+
+    fn get_default_msgid(&self) -> Result<String, StumplessError> {
+        required_string(unsafe { stumpless_get_target_default_msgid(self.get_pointer()) })
+    }
+
+    /// Sets the facility used for messages logged with [`Target::add_message`].
+    fn set_default_facility(&self, facility: Facility) -> Result<(), StumplessError> {
+        set_result(unsafe { stumpless_set_default_facility(self.get_pointer(), facility as i32) })
+    }
+
+    fn get_default_facility(&self) -> Result<Facility, StumplessError> {
+        let facility = unsafe { stumpless_get_default_facility(self.get_pointer()) };
+        if facility < 0 {
+            return Err(last_error());
+        }
+
+        u8::try_from(facility >> 3)
+            .map_err(|_| {
+                StumplessError::new(
+                    ErrorKind::InvalidFacility,
+                    &format!("invalid default facility {}", facility),
+                )
+            })
+            .and_then(Facility::try_from)
+    }
+
+    fn set_option(&self, option: TargetOption) -> Result<(), StumplessError> {
+        set_result(unsafe { stumpless_set_option(self.get_pointer(), option as i32) })
+    }
+
+    fn unset_option(&self, option: TargetOption) -> Result<(), StumplessError> {
+        set_result(unsafe { stumpless_unset_option(self.get_pointer(), option as i32) })
+    }
+
+    fn get_option(&self, option: TargetOption) -> bool {
+        unsafe { stumpless_get_option(self.get_pointer(), option as i32) != 0 }
+    }
+
+    /// Logs a message with the given severity, using the default facility,
+    /// app name, and msgid of the target.
+    ///
+    /// Messages less severe than [`MAX_LEVEL`] are skipped and `Ok(0)` is
+    /// returned.
+    ///
+    /// [`MAX_LEVEL`]: crate::MAX_LEVEL
+    fn log(&self, severity: Severity, message: &str) -> Result<u32, StumplessError> {
+        if !level_enabled(severity) {
+            return Ok(0);
+        }
+
+        let prival = Prival::new(self.get_default_facility()?, severity);
+        let c_message = CString::new(message)?;
+
+        function::dispatch(|| unsafe {
+            stumpless_add_log_str(self.get_pointer(), prival.into(), c_message.as_ptr())
+        })
+    }
+
+    fn emerg(&self, message: &str) -> Result<u32, StumplessError> {
+        self.log(Severity::Emergency, message)
+    }
+
+    fn alert(&self, message: &str) -> Result<u32, StumplessError> {
+        self.log(Severity::Alert, message)
+    }
+
+    fn crit(&self, message: &str) -> Result<u32, StumplessError> {
+        self.log(Severity::Critical, message)
+    }
+
+    fn err(&self, message: &str) -> Result<u32, StumplessError> {
+        self.log(Severity::Error, message)
+    }
+
+    fn warn(&self, message: &str) -> Result<u32, StumplessError> {
+        self.log(Severity::Warning, message)
+    }
+
+    fn notice(&self, message: &str) -> Result<u32, StumplessError> {
+        self.log(Severity::Notice, message)
+    }
+
+    fn info(&self, message: &str) -> Result<u32, StumplessError> {
+        self.log(Severity::Info, message)
+    }
+
+    fn debug(&self, message: &str) -> Result<u32, StumplessError> {
+        self.log(Severity::Debug, message)
+    }
 }
 
+/// A handle to the default target of Stumpless.
+///
+/// There is only one default target in a process, so all handles to it share
+/// the same mask, filter, and other settings. Stumpless keeps the default
+/// target open until the process exits, so dropping or closing a handle leaves
+/// it open.
 pub struct DefaultTarget {
     target: *mut stumpless_target,
 }
 
 impl DefaultTarget {
-    pub fn get_default_target() -> Result<Self, Box<dyn Error>> {
+    pub fn get_default_target() -> Result<Self, StumplessError> {
         let default_target = unsafe { stumpless_get_default_target() };
 
         if default_target.is_null() {
-            match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(Box::new(err)),
-            }
+            Err(last_error())
         } else {
             Ok(DefaultTarget {
                 target: default_target,
@@ -89,10 +285,16 @@ impl DefaultTarget {
     }
 }
 
+unsafe impl Send for DefaultTarget {}
 unsafe impl Sync for DefaultTarget {}
 
 impl Target for DefaultTarget {
     fn get_pointer(&self) -> *mut stumpless_target {
         self.target
     }
+
+    /// Drops this handle, leaving the default target open.
+    fn close(self) -> Result<(), StumplessError> {
+        Ok(())
+    }
 }
@@ -0,0 +1,271 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use stumpless_sys::stumpless_target;
+
+use std::io::{self, Write};
+use std::net::{Shutdown, TcpStream};
+use std::sync::Arc;
+
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use rustls::{ClientConfig, ClientConnection, RootCertStore, StreamOwned};
+
+use crate::entry::Entry;
+use crate::error::{ErrorKind, StumplessError};
+use crate::framed::{self, FramedTarget};
+use crate::protocol::{self, Framing, NetworkProtocol};
+use crate::severity::Severity;
+use crate::Target;
+
+/// A target that sends entries to a server over TLS, as described in RFC 5425.
+///
+/// Each entry is sent using octet counting, and the connection is made again
+/// if sending an entry fails.
+///
+/// ```no_run
+/// # use stumpless::{Target, TlsNetworkTarget};
+/// let target = TlsNetworkTarget::builder("collector.example:6514")
+///     .ca_file("/etc/ssl/certs/collector-ca.pem")
+///     .client_cert("/etc/stumpless/client.pem", "/etc/stumpless/client.key")
+///     .build()
+///     .unwrap();
+/// target.info("sent over TLS").unwrap();
+/// ```
+pub struct TlsNetworkTarget {
+    target: FramedTarget,
+}
+
+impl TlsNetworkTarget {
+    /// The port used by TLS targets unless another one is given.
+    pub const DEFAULT_PORT: &'static str = "6514";
+
+    /// Starts building a target that sends entries to the given server, which
+    /// may include a port as in `example.com:6514`, or `[::1]:6514` for IPv6
+    /// addresses.
+    pub fn builder(server: &str) -> TlsNetworkTargetBuilder {
+        TlsNetworkTargetBuilder::new(server)
+    }
+}
+
+/// Builds a [`TlsNetworkTarget`], which connects to its server once it is
+/// built.
+///
+/// Unless they are changed, targets use port 6514, trust the Mozilla root
+/// certificates, and expect the server to have a certificate for the host
+/// that they connect to.
+pub struct TlsNetworkTargetBuilder {
+    server: String,
+    network: Option<NetworkProtocol>,
+    port: String,
+    ca_file: Option<String>,
+    client_cert: Option<(String, String)>,
+    server_name: Option<String>,
+}
+
+impl TlsNetworkTargetBuilder {
+    pub fn new(server: &str) -> Self {
+        TlsNetworkTargetBuilder {
+            server: server.to_string(),
+            network: None,
+            port: TlsNetworkTarget::DEFAULT_PORT.to_string(),
+            ca_file: None,
+            client_cert: None,
+            server_name: None,
+        }
+    }
+
+    /// Only connects to the server over this IP version.
+    pub fn network(mut self, network: NetworkProtocol) -> Self {
+        self.network = Some(network);
+        self
+    }
+
+    /// Sets the port to send entries to if the server does not include one.
+    pub fn port(mut self, port: &str) -> Self {
+        self.port = port.to_string();
+        self
+    }
+
+    /// Trusts the certificates in the PEM file instead of the Mozilla roots.
+    pub fn ca_file(mut self, ca_file: &str) -> Self {
+        self.ca_file = Some(ca_file.to_string());
+        self
+    }
+
+    /// Authenticates to the server with the certificate chain and private key
+    /// in the given PEM files.
+    pub fn client_cert(mut self, cert_file: &str, key_file: &str) -> Self {
+        self.client_cert = Some((cert_file.to_string(), key_file.to_string()));
+        self
+    }
+
+    /// Sets the name sent to the server with SNI and checked against its
+    /// certificate, if it is different from the host being connected to.
+    pub fn server_name(mut self, server_name: &str) -> Self {
+        self.server_name = Some(server_name.to_string());
+        self
+    }
+
+    /// Loads the certificates and connects to the server, failing if the
+    /// handshake does not succeed.
+    pub fn build(self) -> Result<TlsNetworkTarget, StumplessError> {
+        let (host, port) = protocol::split_address(&self.server).ok_or_else(|| {
+            StumplessError::new(
+                ErrorKind::InvalidAddress,
+                &format!("invalid server address '{}'", self.server),
+            )
+        })?;
+        let port = port.unwrap_or(&self.port).to_string();
+        let host = host.to_string();
+
+        let name = self.server_name.as_deref().unwrap_or(&host);
+        let server_name = ServerName::try_from(name.to_string()).map_err(|_| {
+            StumplessError::new(
+                ErrorKind::InvalidAddress,
+                &format!("invalid server name '{}'", name),
+            )
+        })?;
+
+        let config = Arc::new(self.client_config()?);
+        let network = self.network;
+        let connecting_host = host.clone();
+        let connector = Box::new(move || -> io::Result<framed::Stream> {
+            let socket = framed::connect_tcp(&connecting_host, &port, network)?;
+            let connection = ClientConnection::new(Arc::clone(&config), server_name.clone())
+                .map_err(io::Error::other)?;
+            let mut stream = StreamOwned::new(connection, socket);
+            while stream.conn.is_handshaking() {
+                stream.conn.complete_io(&mut stream.sock)?;
+            }
+            Ok(Box::new(TlsStream(stream)))
+        });
+
+        let target = FramedTarget::new(&host, Framing::OctetCounting, connector)?;
+        Ok(TlsNetworkTarget { target })
+    }
+
+    fn client_config(&self) -> Result<ClientConfig, StumplessError> {
+        let mut roots = RootCertStore::empty();
+        match &self.ca_file {
+            Some(ca_file) => {
+                for cert in read_certs(ca_file)? {
+                    roots.add(cert).map_err(|err| tls_error(ca_file, err))?;
+                }
+            }
+            None => roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned()),
+        }
+
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let builder = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .map_err(|err| tls_error("the protocol versions", err))?
+            .with_root_certificates(roots);
+
+        match &self.client_cert {
+            Some((cert_file, key_file)) => {
+                let certs = read_certs(cert_file)?;
+                let key = PrivateKeyDer::from_pem_file(key_file)
+                    .map_err(|err| tls_error(key_file, err))?;
+                builder
+                    .with_client_auth_cert(certs, key)
+                    .map_err(|err| tls_error(cert_file, err))
+            }
+            None => Ok(builder.with_no_client_auth()),
+        }
+    }
+}
+
+fn tls_error(source: &str, err: impl std::fmt::Display) -> StumplessError {
+    StumplessError::new(
+        ErrorKind::TlsFailure,
+        &format!("could not use {}: {}", source, err),
+    )
+}
+
+fn read_certs(filename: &str) -> Result<Vec<CertificateDer<'static>>, StumplessError> {
+    let certs = CertificateDer::pem_file_iter(filename)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|err| tls_error(filename, err))?;
+
+    if certs.is_empty() {
+        Err(tls_error(filename, "no certificates found"))
+    } else {
+        Ok(certs)
+    }
+}
+
+struct TlsStream(StreamOwned<ClientConnection, TcpStream>);
+
+impl Write for TlsStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl Drop for TlsStream {
+    fn drop(&mut self) {
+        // Nothing here may wait on the server, as the connection lock is held,
+        // so the socket is made non-blocking first. If that fails the socket is
+        // just shut down.
+        if self.0.sock.set_nonblocking(true).is_ok() {
+            // lets the server tell a closed connection from a truncated one
+            self.0.conn.send_close_notify();
+            while self.0.conn.wants_write() {
+                if self.0.conn.write_tls(&mut self.0.sock).is_err() {
+                    break;
+                }
+            }
+
+            // Closing a socket that still has unread data in it, such as
+            // session tickets sent after the handshake, resets the connection
+            // and can lose the last messages, so whatever has already arrived
+            // is read first.
+            let _ = io::copy(&mut self.0.sock, &mut io::sink());
+        }
+        let _ = self.0.sock.shutdown(Shutdown::Write);
+    }
+}
+
+impl Target for TlsNetworkTarget {
+    fn get_pointer(&self) -> *mut stumpless_target {
+        self.target.target().get_pointer()
+    }
+
+    fn add_entry(&self, entry: &Entry) -> Result<u32, StumplessError> {
+        self.target.target().add_entry(entry)
+    }
+
+    fn add_message(&self, message: &str) -> Result<u32, StumplessError> {
+        self.target.target().add_message(message)
+    }
+
+    fn log(&self, severity: Severity, message: &str) -> Result<u32, StumplessError> {
+        self.target.target().log(severity, message)
+    }
+
+    fn open(&self) -> Result<(), StumplessError> {
+        self.target.reconnect()
+    }
+
+    fn reopen(&self) -> Result<(), StumplessError> {
+        self.open()
+    }
+}
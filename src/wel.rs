@@ -19,10 +19,10 @@ use stumpless_sys::{
     stumpless_open_local_wel_target, stumpless_target,
 };
 
-use std::error::Error;
 use std::ffi::CString;
 
-use crate::error::last_error;
+use crate::error::{last_error, StumplessError};
+use crate::filter;
 use crate::Target;
 
 pub struct WelTarget {
@@ -30,21 +30,19 @@ pub struct WelTarget {
 }
 
 impl WelTarget {
-    pub fn new(log_name: &str) -> Result<Self, Box<dyn Error>> {
+    pub fn new(log_name: &str) -> Result<Self, StumplessError> {
         let c_log_name = CString::new(log_name)?;
         let wel_target = unsafe { stumpless_open_local_wel_target(c_log_name.as_ptr()) };
 
         if wel_target.is_null() {
-            match last_error() {
-                Ok(_success) => panic!("inconsistent stumpless error state"),
-                Err(err) => Err(Box::new(err)),
-            }
+            Err(last_error())
         } else {
             Ok(WelTarget { target: wel_target })
         }
     }
 }
 
+unsafe impl Send for WelTarget {}
 unsafe impl Sync for WelTarget {}
 
 impl Target for WelTarget {
@@ -55,21 +53,19 @@ impl Target for WelTarget {
 
 impl Drop for WelTarget {
     fn drop(&mut self) {
+        filter::remove_filter(self.target);
         unsafe {
             stumpless_close_wel_target(self.target);
         }
     }
 }
 
-pub fn add_default_wel_event_source() -> Result<u32, Box<dyn Error>> {
+pub fn add_default_wel_event_source() -> Result<u32, StumplessError> {
     let add_result = unsafe { stumpless_add_default_wel_event_source() };
 
     if add_result == 0 {
         Ok(add_result.try_into().unwrap())
     } else {
-        match last_error() {
-            Ok(_success) => panic!("inconsistent stumpless error state"),
-            Err(err) => Err(Box::new(err)),
-        }
+        Err(last_error())
     }
 }
@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::future::Future;
+use std::thread;
+
+use tokio::sync::{mpsc, oneshot};
+
+use crate::entry::Entry;
+use crate::error::{ErrorKind, StumplessError};
+use crate::level::level_enabled;
+use crate::severity::Severity;
+use crate::Target;
+
+/// A target that can be logged to from async code without blocking the
+/// executor.
+pub trait AsyncTarget {
+    fn add_entry(&self, entry: &Entry) -> impl Future<Output = Result<u32, StumplessError>> + Send;
+
+    fn add_message(
+        &self,
+        message: &str,
+    ) -> impl Future<Output = Result<u32, StumplessError>> + Send;
+
+    /// Logs a message with the given severity, in the same way as
+    /// [`Target::log`].
+    fn log(
+        &self,
+        severity: Severity,
+        message: &str,
+    ) -> impl Future<Output = Result<u32, StumplessError>> + Send;
+
+    /// Flushes the target, in the same way as [`Target::flush`].
+    fn flush(&self) -> impl Future<Output = Result<(), StumplessError>> + Send;
+}
+
+enum Request {
+    Entry(Entry),
+    Message(String),
+    Log(Severity, String),
+    Flush,
+    Close,
+}
+
+type Reply = oneshot::Sender<Result<u32, StumplessError>>;
+
+fn worker_stopped() -> StumplessError {
+    StumplessError::new(ErrorKind::WorkerStopped, "the target worker has stopped")
+}
+
+/// An [`AsyncTarget`] that moves a target onto its own thread.
+///
+/// Each call is queued for the thread, which logs to the target one request
+/// at a time. Once the queue is full, calls wait for room in it instead of
+/// blocking, so a slow target such as a network server holds up the tasks
+/// that log to it but not the executor they run on.
+///
+/// Dropping the worker lets the thread log whatever is already queued and
+/// then close the target. Use [`WorkerTarget::close`] to wait for this and get
+/// the result.
+///
+/// ```no_run
+/// # use stumpless::{AsyncTarget, FileTarget, WorkerTarget};
+/// # async fn example() {
+/// let target = WorkerTarget::new(FileTarget::new("app.log").unwrap(), 64).unwrap();
+/// target.add_message("logged without blocking").await.unwrap();
+/// target.close().await.unwrap();
+/// # }
+/// ```
+pub struct WorkerTarget {
+    requests: mpsc::Sender<(Request, Reply)>,
+}
+
+impl WorkerTarget {
+    /// Starts a thread for the target, with room for `capacity` requests to be
+    /// queued for it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new<T>(target: T, capacity: usize) -> Result<Self, StumplessError>
+    where
+        T: Target + Send + 'static,
+    {
+        let (requests, mut receiver) = mpsc::channel::<(Request, Reply)>(capacity);
+
+        thread::Builder::new()
+            .name("stumpless-worker".to_string())
+            .spawn(move || {
+                let mut close_reply = None;
+
+                while let Some((request, reply)) = receiver.blocking_recv() {
+                    let result = match request {
+                        Request::Entry(entry) => target.add_entry(&entry),
+                        Request::Message(message) => target.add_message(&message),
+                        Request::Log(severity, message) => target.log(severity, &message),
+                        Request::Flush => target.flush().map(|()| 0),
+                        Request::Close => {
+                            close_reply = Some(reply);
+                            break;
+                        }
+                    };
+
+                    // the caller may have stopped waiting for the result
+                    let _ = reply.send(result);
+                }
+
+                let close_result = target.close().map(|()| 0);
+                if let Some(reply) = close_reply {
+                    let _ = reply.send(close_result);
+                }
+            })?;
+
+        Ok(WorkerTarget { requests })
+    }
+
+    /// Waits for everything already queued to be logged, and then closes the
+    /// target in the same way as [`Target::close`].
+    pub async fn close(self) -> Result<(), StumplessError> {
+        self.request(Request::Close).await.map(|_| ())
+    }
+
+    async fn request(&self, request: Request) -> Result<u32, StumplessError> {
+        let (reply, result) = oneshot::channel();
+
+        self.requests
+            .send((request, reply))
+            .await
+            .map_err(|_| worker_stopped())?;
+
+        result.await.map_err(|_| worker_stopped())?
+    }
+}
+
+impl AsyncTarget for WorkerTarget {
+    async fn add_entry(&self, entry: &Entry) -> Result<u32, StumplessError> {
+        self.request(Request::Entry(entry.try_clone()?)).await
+    }
+
+    async fn add_message(&self, message: &str) -> Result<u32, StumplessError> {
+        self.request(Request::Message(message.to_string())).await
+    }
+
+    async fn log(&self, severity: Severity, message: &str) -> Result<u32, StumplessError> {
+        if !level_enabled(severity) {
+            return Ok(0);
+        }
+
+        self.request(Request::Log(severity, message.to_string()))
+            .await
+    }
+
+    async fn flush(&self) -> Result<(), StumplessError> {
+        self.request(Request::Flush).await.map(|_| ())
+    }
+}
@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use stumpless::{BufferTarget, Entry, ErrorKind, Target};
+
+// entries without a hostname or procid so that every message has the same
+// length, which is 51 characters for single digit numbers
+fn numbered_entry(number: usize) -> Entry {
+    Entry::builder()
+        .app_name("app")
+        .nil_hostname()
+        .nil_procid()
+        .message(&format!("msg-{}", number))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn empty_buffer_reads_nothing() {
+    let mut buffer = [0u8; 128];
+    let target = BufferTarget::new("empty", &mut buffer).unwrap();
+
+    assert_eq!(target.read().unwrap(), None);
+    assert!(target.read_all().unwrap().is_empty());
+}
+
+#[test]
+fn messages_are_read_in_order() {
+    let mut buffer = [0u8; 1024];
+    let target = BufferTarget::new("ordered", &mut buffer).unwrap();
+
+    for i in 1..=3 {
+        target.add_entry(&numbered_entry(i)).unwrap();
+    }
+
+    let messages = target.read_all().unwrap();
+    assert_eq!(messages.len(), 3);
+    for (i, message) in messages.iter().enumerate() {
+        assert!(message.starts_with("<14>1 "), "{}", message);
+        assert!(
+            message.ends_with(&format!(" - app - - - msg-{}", i + 1)),
+            "{}",
+            message
+        );
+    }
+    assert_eq!(target.read().unwrap(), None);
+}
+
+#[test]
+fn message_wrapping_around_the_end_is_read_whole() {
+    let mut buffer = [0u8; 100];
+    let target = BufferTarget::new("wrapped", &mut buffer).unwrap();
+
+    target.add_entry(&numbered_entry(1)).unwrap();
+    let first = target.read().unwrap().unwrap();
+    assert_eq!(first.len(), 51);
+
+    // only 48 bytes are left before the end of the buffer
+    target.add_entry(&numbered_entry(2)).unwrap();
+    let second = target.read().unwrap().unwrap();
+    assert_eq!(second.len(), 51);
+    assert!(second.ends_with(" msg-2"), "{}", second);
+    assert_eq!(target.read().unwrap(), None);
+}
+
+#[test]
+fn unread_messages_are_overwritten() {
+    let mut buffer = [0u8; 100];
+    let target = BufferTarget::new("overwritten", &mut buffer).unwrap();
+
+    for i in 1..=3 {
+        target.add_entry(&numbered_entry(i)).unwrap();
+    }
+
+    let messages = target.read_all().unwrap();
+    assert!(!messages.iter().any(|message| message.ends_with(" msg-1")));
+    assert!(messages.last().unwrap().ends_with(" msg-3"));
+    assert_eq!(messages.last().unwrap().len(), 51);
+}
+
+#[test]
+fn message_larger_than_buffer_is_rejected() {
+    let mut buffer = [0u8; 32];
+    let target = BufferTarget::new("small", &mut buffer).unwrap();
+
+    let err = target.add_entry(&numbered_entry(1)).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::ArgumentTooBig);
+    assert_eq!(target.read().unwrap(), None);
+}
@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use stumpless::{ChainTarget, ErrorKind, FunctionTarget, Severity, StumplessError, Target};
+
+mod common;
+use common::{entry, recording_target};
+
+fn failing_target(name: &str) -> Box<dyn Target + Send> {
+    let target = FunctionTarget::new(name, |_| {
+        Err(StumplessError::new(
+            ErrorKind::FunctionTargetFailure,
+            "rejected",
+        ))
+    })
+    .unwrap();
+
+    Box::new(target)
+}
+
+#[test]
+fn entries_go_to_every_target() {
+    let (first, first_messages) = recording_target("chain-first");
+    let (second, second_messages) = recording_target("chain-second");
+    let chain = ChainTarget::new("chain", vec![Box::new(first), Box::new(second)]).unwrap();
+
+    chain
+        .add_entry(&entry("chain", Severity::Info, "fanned out"))
+        .unwrap();
+    chain.add_message("as a message").unwrap();
+
+    assert_eq!(
+        *first_messages.lock().unwrap(),
+        ["fanned out", "as a message"]
+    );
+    assert_eq!(
+        *second_messages.lock().unwrap(),
+        ["fanned out", "as a message"]
+    );
+}
+
+#[test]
+fn report_has_result_for_each_target() {
+    let (first, first_messages) = recording_target("report-first");
+    let (last, last_messages) = recording_target("report-last");
+    let chain = ChainTarget::new(
+        "report",
+        vec![Box::new(first), failing_target("report-fail")],
+    )
+    .unwrap();
+    chain.push(Box::new(last));
+
+    let report = chain
+        .add_entry_report(&entry("chain", Severity::Info, "reported"))
+        .unwrap();
+
+    assert_eq!(report.results().len(), 3);
+    assert!(!report.is_ok());
+    let errors: Vec<usize> = report.errors().map(|(i, _)| i).collect();
+    assert_eq!(errors, [1]);
+    assert_eq!(*first_messages.lock().unwrap(), ["reported"]);
+    assert_eq!(*last_messages.lock().unwrap(), ["reported"]);
+
+    let err = chain
+        .add_entry(&entry("chain", Severity::Info, "failed"))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::FunctionTargetFailure);
+}
+
+#[test]
+fn chain_mask_applies_before_targets() {
+    let (member, messages) = recording_target("mask-member");
+    let chain = ChainTarget::new("mask", vec![Box::new(member)]).unwrap();
+    chain.set_mask(Severity::Error.mask_upto()).unwrap();
+
+    let report = chain
+        .add_entry_report(&entry("chain", Severity::Info, "masked"))
+        .unwrap();
+    chain
+        .add_entry(&entry("chain", Severity::Error, "logged"))
+        .unwrap();
+
+    assert!(report.results().is_empty());
+    assert_eq!(*messages.lock().unwrap(), ["logged"]);
+}
+
+#[test]
+fn chains_can_be_nested() {
+    let (member, messages) = recording_target("nested-member");
+    let inner = ChainTarget::new("inner", vec![Box::new(member)]).unwrap();
+    let outer = ChainTarget::new("outer", vec![Box::new(inner)]).unwrap();
+
+    let report = outer
+        .add_entry_report(&entry("chain", Severity::Info, "nested"))
+        .unwrap();
+
+    assert!(report.is_ok());
+    assert_eq!(report.results().len(), 1);
+    assert_eq!(*messages.lock().unwrap(), ["nested"]);
+}
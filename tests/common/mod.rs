@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// helpers shared by the integration tests, not all of which use every one
+#![allow(dead_code)]
+
+use std::sync::{Arc, Mutex};
+
+use stumpless::{Entry, FunctionTarget, Severity};
+use tempfile::TempDir;
+
+pub type Messages = Arc<Mutex<Vec<String>>>;
+
+pub type Entries = Arc<Mutex<Vec<Entry>>>;
+
+// a target that keeps the message of each entry logged to it
+pub fn recording_target(name: &str) -> (FunctionTarget, Messages) {
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&messages);
+    let target = FunctionTarget::new(name, move |entry| {
+        recorded
+            .lock()
+            .unwrap()
+            .push(entry.message()?.unwrap_or_default());
+        Ok(())
+    })
+    .unwrap();
+
+    (target, messages)
+}
+
+// a target that keeps a copy of each entry logged to it
+pub fn entry_target(name: &str) -> (FunctionTarget, Entries) {
+    let entries = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&entries);
+    let target = FunctionTarget::new(name, move |entry| {
+        recorded.lock().unwrap().push(entry.try_clone()?);
+        Ok(())
+    })
+    .unwrap();
+
+    (target, entries)
+}
+
+pub fn entry(app_name: &str, severity: Severity, message: &str) -> Entry {
+    Entry::builder()
+        .app_name(app_name)
+        .severity(severity)
+        .message(message)
+        .build()
+        .unwrap()
+}
+
+// a directory for the files that a test logs to, which is removed along with
+// them when it is dropped
+pub fn log_dir() -> TempDir {
+    tempfile::tempdir().unwrap()
+}
+
+pub fn log_path(dir: &TempDir, name: &str) -> String {
+    let path = dir.path().join(format!("{}.log", name));
+    path.to_str().unwrap().to_string()
+}
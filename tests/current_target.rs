@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use stumpless::Severity;
+
+mod common;
+use common::recording_target;
+
+// the current target is global, so the tests that change it take turns
+static CURRENT_TARGET_LOCK: Mutex<()> = Mutex::new(());
+
+#[test]
+fn log_goes_to_current_target() {
+    let _lock = CURRENT_TARGET_LOCK.lock().unwrap();
+    let (target, messages) = recording_target("current");
+    stumpless::set_current_target(Arc::new(target));
+
+    stumpless::log(Severity::Error, "first").unwrap();
+    thread::spawn(|| stumpless::log(Severity::Error, "from a thread").unwrap())
+        .join()
+        .unwrap();
+
+    assert_eq!(*messages.lock().unwrap(), ["first", "from a thread"]);
+    stumpless::clear_current_target();
+}
+
+#[test]
+fn current_target_is_kept_open() {
+    let _lock = CURRENT_TARGET_LOCK.lock().unwrap();
+    let (target, messages) = recording_target("kept-open");
+    stumpless::set_current_target(Arc::new(target));
+
+    // the only remaining reference is the current target itself
+    let (_other_target, _) = recording_target("opened-later");
+    stumpless::current_target()
+        .unwrap()
+        .err("still open")
+        .unwrap();
+
+    assert_eq!(*messages.lock().unwrap(), ["still open"]);
+    stumpless::clear_current_target();
+}
+
+#[test]
+fn replaced_target_is_returned() {
+    let _lock = CURRENT_TARGET_LOCK.lock().unwrap();
+    let (first, first_messages) = recording_target("first");
+    let (second, second_messages) = recording_target("second");
+
+    stumpless::set_current_target(Arc::new(first));
+    let previous = stumpless::set_current_target(Arc::new(second)).unwrap();
+    previous.err("to the first").unwrap();
+    stumpless::log(Severity::Error, "to the second").unwrap();
+
+    assert_eq!(*first_messages.lock().unwrap(), ["to the first"]);
+    assert_eq!(*second_messages.lock().unwrap(), ["to the second"]);
+    assert!(stumpless::clear_current_target().is_some());
+    assert!(stumpless::clear_current_target().is_none());
+}
+
+#[test]
+fn default_target_is_shared() {
+    let _lock = CURRENT_TARGET_LOCK.lock().unwrap();
+    stumpless::clear_current_target();
+
+    let first = stumpless::current_target().unwrap();
+    let second = stumpless::current_target().unwrap();
+    assert!(Arc::ptr_eq(&first, &second));
+
+    let (target, _) = recording_target("over-default");
+    stumpless::set_current_target(Arc::new(target));
+    assert!(!Arc::ptr_eq(&stumpless::current_target().unwrap(), &first));
+
+    stumpless::clear_current_target();
+    assert!(Arc::ptr_eq(&stumpless::current_target().unwrap(), &first));
+}
@@ -0,0 +1,353 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, UNIX_EPOCH};
+
+use stumpless::{
+    Element, Entry, EntryTemplate, ErrorKind, Facility, FunctionTarget, Param, Severity, Target,
+};
+
+#[test]
+fn unset_fields_are_nil() {
+    let entry = Entry::builder().build().unwrap();
+
+    assert_eq!(
+        entry.prival(),
+        Facility::User as i32 | Severity::Info as i32
+    );
+    assert_eq!(entry.app_name().unwrap(), "-");
+    assert_eq!(entry.msgid().unwrap(), "-");
+    assert_eq!(entry.message().unwrap(), None);
+    assert_eq!(entry.timestamp(), None);
+}
+
+#[test]
+fn hostname_and_procid_default_to_the_system() {
+    let entry = Entry::builder().build().unwrap();
+
+    assert_ne!(entry.hostname().unwrap(), "-");
+    assert_eq!(entry.procid().unwrap(), std::process::id().to_string());
+}
+
+#[test]
+fn fields_can_be_made_nil() {
+    let entry = Entry::builder()
+        .app_name("app")
+        .nil_app_name()
+        .hostname("host")
+        .nil_hostname()
+        .procid("42")
+        .nil_procid()
+        .msgid("msgid")
+        .nil_msgid()
+        .message("message")
+        .no_message()
+        .build()
+        .unwrap();
+
+    assert_eq!(entry.app_name().unwrap(), "-");
+    assert_eq!(entry.hostname().unwrap(), "-");
+    assert_eq!(entry.procid().unwrap(), "-");
+    assert_eq!(entry.msgid().unwrap(), "-");
+    assert_eq!(entry.message().unwrap(), None);
+}
+
+#[test]
+fn fields_are_set() {
+    let entry = Entry::builder()
+        .facility(Facility::Local3)
+        .severity(Severity::Error)
+        .app_name("app")
+        .hostname("host.example")
+        .procid("worker-7")
+        .msgid("msgid")
+        .message("message")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        entry.prival(),
+        Facility::Local3 as i32 | Severity::Error as i32
+    );
+    assert_eq!(entry.app_name().unwrap(), "app");
+    assert_eq!(entry.hostname().unwrap(), "host.example");
+    assert_eq!(entry.procid().unwrap(), "worker-7");
+    assert_eq!(entry.msgid().unwrap(), "msgid");
+    assert_eq!(entry.message().unwrap().as_deref(), Some("message"));
+}
+
+#[test]
+fn empty_fields_are_rejected() {
+    let err = Entry::builder().app_name("").build().unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::ArgumentEmpty);
+}
+
+#[test]
+fn long_fields_are_rejected() {
+    let fields = [
+        Entry::builder().app_name(&"a".repeat(49)),
+        Entry::builder().hostname(&"h".repeat(256)),
+        Entry::builder().procid(&"p".repeat(129)),
+        Entry::builder().msgid(&"m".repeat(33)),
+    ];
+
+    for builder in fields {
+        assert_eq!(
+            builder.build().unwrap_err().kind(),
+            ErrorKind::ArgumentTooBig
+        );
+    }
+
+    assert!(Entry::builder().app_name(&"a".repeat(48)).build().is_ok());
+    assert!(Entry::builder().msgid(&"m".repeat(32)).build().is_ok());
+}
+
+#[test]
+fn unprintable_fields_are_rejected() {
+    let fields = [
+        Entry::builder().app_name("has space"),
+        Entry::builder().hostname("tab\there"),
+        Entry::builder().procid("caf\u{e9}"),
+        Entry::builder().msgid("new\nline"),
+    ];
+
+    for builder in fields {
+        assert_eq!(
+            builder.build().unwrap_err().kind(),
+            ErrorKind::InvalidEncoding
+        );
+    }
+}
+
+#[test]
+fn nul_in_message_is_rejected() {
+    let err = Entry::builder().message("nul\0byte").build().unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidEncoding);
+}
+
+#[test]
+fn explicit_timestamp_is_displayed() {
+    let timestamp = UNIX_EPOCH + Duration::from_micros(1_700_000_000_123_456);
+    let entry = Entry::builder()
+        .app_name("app")
+        .timestamp(timestamp)
+        .build()
+        .unwrap();
+
+    assert_eq!(entry.timestamp(), Some(timestamp));
+    assert!(entry.to_string().contains(" 2023-11-14T22:13:20.123456Z "));
+    assert_eq!(entry.clone().timestamp(), Some(timestamp));
+}
+
+#[test]
+fn explicit_timestamp_reaches_function_targets() {
+    let timestamp = UNIX_EPOCH + Duration::from_secs(86400);
+    let target = FunctionTarget::new("timestamp", move |entry| {
+        assert_eq!(entry.timestamp(), Some(timestamp));
+        Ok(())
+    })
+    .unwrap();
+
+    let mut entry = Entry::builder().build().unwrap();
+    entry.set_timestamp(timestamp);
+    target.add_entry(&entry).unwrap();
+}
+
+fn structured_entry() -> Entry {
+    let mut entry = Entry::builder()
+        .facility(Facility::Local0)
+        .severity(Severity::Notice)
+        .app_name("app")
+        .hostname("host")
+        .msgid("msgid")
+        .message("structured")
+        .timestamp(UNIX_EPOCH + Duration::from_secs(1))
+        .build()
+        .unwrap();
+    entry
+        .add_new_element("first")
+        .unwrap()
+        .add_new_param("first", "name", "value")
+        .unwrap()
+        .add_new_element("second")
+        .unwrap();
+    entry
+}
+
+#[test]
+fn getters_read_the_entry() {
+    let entry = structured_entry();
+
+    assert_eq!(
+        entry.prival(),
+        Facility::Local0 as i32 | Severity::Notice as i32
+    );
+    assert_eq!(entry.app_name().unwrap(), "app");
+    assert_eq!(entry.hostname().unwrap(), "host");
+    assert_eq!(entry.msgid().unwrap(), "msgid");
+    assert_eq!(entry.message().unwrap().as_deref(), Some("structured"));
+    assert_eq!(entry.element_count(), 2);
+    assert!(entry.has_element("first"));
+    assert!(!entry.has_element("third"));
+    assert_eq!(entry.element_names().unwrap(), ["first", "second"]);
+    assert_eq!(entry.element("first").unwrap().param_count(), 1);
+    assert_eq!(entry.param_value("first", "name").unwrap(), "value");
+    assert_eq!(
+        entry.param_value("first", "missing").unwrap_err().kind(),
+        ErrorKind::ParamNotFound
+    );
+    assert_eq!(
+        entry.element("third").unwrap_err().kind(),
+        ErrorKind::ElementNotFound
+    );
+}
+
+#[test]
+fn display_renders_the_message() {
+    let entry = structured_entry();
+
+    assert_eq!(
+        entry.to_string(),
+        "<133>1 1970-01-01T00:00:01.000000Z host app - msgid \
+         [first name=\"value\"][second] structured"
+    );
+}
+
+#[test]
+fn display_leaves_out_procid() {
+    let entry = Entry::builder()
+        .hostname("host")
+        .procid("worker-7")
+        .timestamp(UNIX_EPOCH)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        entry.to_string(),
+        "<14>1 1970-01-01T00:00:00.000000Z host - - - -"
+    );
+}
+
+#[test]
+fn display_matches_formatted_targets() {
+    let mut entry = Entry::builder()
+        .hostname("host")
+        .timestamp(UNIX_EPOCH)
+        .message("matching")
+        .build()
+        .unwrap();
+    let mut element = Element::new("quoted").unwrap();
+    element
+        .add_param(&Param::new("value", r#"a"b\c]d"#).unwrap())
+        .unwrap();
+    entry.add_element(&element).unwrap();
+
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&messages);
+    let target = FunctionTarget::formatted("display", move |message| {
+        recorded
+            .lock()
+            .unwrap()
+            .push(String::from_utf8_lossy(message).into_owned());
+        Ok(())
+    })
+    .unwrap();
+    target.add_entry(&entry).unwrap();
+
+    assert_eq!(messages.lock().unwrap()[..], [entry.to_string()]);
+}
+
+#[test]
+fn debug_shows_the_fields() {
+    let debug = format!("{:?}", structured_entry());
+
+    assert!(debug.starts_with("Entry { prival: 133"));
+    assert!(debug.contains("app_name: \"app\""));
+    assert!(debug.contains("name: \"first\""));
+    assert!(debug.contains("message: Some(\"structured\")"));
+}
+
+#[test]
+fn clone_keeps_format_specifiers_in_the_message() {
+    let entry = Entry::builder().message("%s %n 100%").build().unwrap();
+
+    let copy = entry.try_clone().unwrap();
+
+    assert_eq!(copy.message().unwrap().as_deref(), Some("%s %n 100%"));
+}
+
+#[test]
+fn clone_copies_every_field() {
+    let mut entry = Entry::builder()
+        .facility(Facility::Daemon)
+        .severity(Severity::Warning)
+        .app_name("app")
+        .hostname("host")
+        .procid("42")
+        .msgid("msgid")
+        .message("original")
+        .build()
+        .unwrap();
+    entry
+        .add_new_element("element")
+        .unwrap()
+        .add_new_param("element", "name", "value")
+        .unwrap();
+
+    let copy = entry.clone();
+
+    assert_eq!(copy.prival(), entry.prival());
+    assert_eq!(copy.app_name().unwrap(), "app");
+    assert_eq!(copy.hostname().unwrap(), "host");
+    assert_eq!(copy.procid().unwrap(), "42");
+    assert_eq!(copy.msgid().unwrap(), "msgid");
+    assert_eq!(copy.param_value("element", "name").unwrap(), "value");
+
+    entry.set_param_value("element", "name", "changed").unwrap();
+    assert_eq!(copy.param_value("element", "name").unwrap(), "value");
+}
+
+#[test]
+fn clone_keeps_nil_fields() {
+    let entry = Entry::builder()
+        .nil_hostname()
+        .no_message()
+        .build()
+        .unwrap();
+
+    let copy = entry.clone();
+
+    assert_eq!(copy.app_name().unwrap(), "-");
+    assert_eq!(copy.hostname().unwrap(), "-");
+    assert_eq!(copy.message().unwrap(), None);
+}
+
+#[test]
+fn template_entries_share_structured_data() {
+    let mut prototype = Entry::builder().app_name("template").build().unwrap();
+    prototype.add_new_element("fixed").unwrap();
+    let template = EntryTemplate::from(prototype);
+
+    let entry = template.entry("100% done").unwrap();
+
+    assert_eq!(entry.message().unwrap().as_deref(), Some("100% done"));
+    assert_eq!(entry.app_name().unwrap(), "template");
+    assert!(entry.has_element("fixed"));
+    assert_eq!(template.prototype().message().unwrap(), None);
+}
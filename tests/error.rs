@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ffi::CString;
+use std::io;
+
+use stumpless::{Element, ErrorKind, Facility, Severity, StumplessError};
+
+#[test]
+fn library_errors_keep_their_id() {
+    let err = Element::new("bad name").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidEncoding);
+    assert!(err.id().is_some());
+    assert!(!err.message().is_empty());
+}
+
+#[test]
+fn crate_errors_have_no_id() {
+    let err = "not-a-facility".parse::<Facility>().unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidFacility);
+    assert_eq!(err.id(), None);
+    assert_eq!(err.code(), None);
+    assert_eq!(err.code_type(), None);
+
+    let err = "not-a-severity".parse::<Severity>().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidSeverity);
+}
+
+#[test]
+fn display_includes_code() {
+    let err = StumplessError::new(ErrorKind::Unknown, "no code");
+    assert_eq!(err.to_string(), "no code");
+
+    let err = StumplessError::from(io::Error::from_raw_os_error(2));
+    assert_eq!(err.code(), Some(2));
+    assert_eq!(err.code_type(), Some("os error"));
+    assert!(err.to_string().ends_with(" (os error: 2)"));
+}
+
+#[test]
+fn nul_error_is_invalid_encoding() {
+    let err = StumplessError::from(CString::new("interior\0nul").unwrap_err());
+
+    assert_eq!(err.kind(), ErrorKind::InvalidEncoding);
+}
+
+#[test]
+fn io_error_becomes_io_kind() {
+    let err = StumplessError::from(io::Error::new(io::ErrorKind::TimedOut, "took too long"));
+
+    assert_eq!(err.kind(), ErrorKind::Io);
+    assert_eq!(err.message(), "took too long");
+    assert_eq!(err.code(), None);
+}
+
+#[test]
+fn io_kind_follows_error_kind() {
+    let kinds = [
+        (ErrorKind::ArgumentEmpty, io::ErrorKind::InvalidInput),
+        (ErrorKind::InvalidPrival, io::ErrorKind::InvalidInput),
+        (ErrorKind::DuplicateElement, io::ErrorKind::InvalidInput),
+        (ErrorKind::InvalidEncoding, io::ErrorKind::InvalidData),
+        (ErrorKind::ParamNotFound, io::ErrorKind::NotFound),
+        (
+            ErrorKind::MemoryAllocationFailure,
+            io::ErrorKind::OutOfMemory,
+        ),
+        (ErrorKind::TargetUnsupported, io::ErrorKind::Unsupported),
+        (
+            ErrorKind::SocketConnectFailure,
+            io::ErrorKind::ConnectionRefused,
+        ),
+        (ErrorKind::WorkerStopped, io::ErrorKind::BrokenPipe),
+        (ErrorKind::Unknown, io::ErrorKind::Other),
+    ];
+
+    for (kind, io_kind) in kinds {
+        let err = io::Error::from(StumplessError::new(kind, "converted"));
+        assert_eq!(err.kind(), io_kind, "{:?}", kind);
+        assert_eq!(err.to_string(), "converted");
+    }
+}
+
+#[test]
+fn io_error_round_trips() {
+    let original = io::Error::from_raw_os_error(13);
+    let err = io::Error::from(StumplessError::from(original));
+
+    assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+    assert!(err.get_ref().unwrap().is::<StumplessError>());
+}
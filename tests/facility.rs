@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use stumpless::{ErrorKind, Facility};
+
+#[test]
+fn names_parse_ignoring_case() {
+    assert_eq!("kern".parse::<Facility>().unwrap(), Facility::Kernel);
+    assert_eq!("USER".parse::<Facility>().unwrap(), Facility::User);
+    assert_eq!("Local7".parse::<Facility>().unwrap(), Facility::Local7);
+}
+
+#[test]
+fn aliases_parse() {
+    let aliases = [
+        ("security", Facility::Auth),
+        ("auth2", Facility::Auth2),
+        ("authpriv", Facility::Auth2),
+        ("audit", Facility::Audit),
+        ("alert", Facility::Alert),
+        ("cron2", Facility::Cron2),
+    ];
+
+    for (alias, facility) in aliases {
+        assert_eq!(alias.parse::<Facility>().unwrap(), facility, "{}", alias);
+    }
+}
+
+#[test]
+fn unknown_names_are_rejected() {
+    for name in ["", "local8", "kernel", " user", "4"] {
+        let err = name.parse::<Facility>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidFacility, "{:?}", name);
+    }
+}
+
+#[test]
+fn display_uses_canonical_names() {
+    assert_eq!("authpriv".parse::<Facility>().unwrap().to_string(), "auth2");
+    assert_eq!("security".parse::<Facility>().unwrap().to_string(), "auth");
+    assert_eq!(Facility::Kernel.to_string(), "kern");
+}
+
+#[test]
+fn every_code_round_trips() {
+    for code in 0..=23u8 {
+        let facility = Facility::try_from(code).unwrap();
+        assert_eq!(facility.code(), code);
+        assert_eq!(u8::from(facility), code);
+        assert_eq!(facility.name().parse::<Facility>().unwrap(), facility);
+    }
+
+    assert_eq!(Facility::Local0.code(), 16);
+    assert_eq!(
+        Facility::try_from(24).unwrap_err().kind(),
+        ErrorKind::InvalidFacility
+    );
+}
+
+#[test]
+fn facilities_are_ordered_by_code() {
+    assert!(Facility::Kernel < Facility::User);
+    assert!(Facility::Cron2 < Facility::Local0);
+    assert_eq!(Facility::Local7.max(Facility::Mail), Facility::Local7);
+}
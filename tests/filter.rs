@@ -0,0 +1,133 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use stumpless::{BufferTarget, Severity, Target};
+
+mod common;
+use common::entry;
+
+#[test]
+fn filter_drops_rejected_entries() {
+    let mut buffer = [0u8; 4096];
+    let target = BufferTarget::new("filter", &mut buffer).unwrap();
+    target
+        .set_filter(|entry| entry.app_name().unwrap() != "noisy")
+        .unwrap();
+
+    target
+        .add_entry(&entry("quiet", Severity::Info, "kept"))
+        .unwrap();
+    target
+        .add_entry(&entry("noisy", Severity::Info, "dropped"))
+        .unwrap();
+
+    let messages = target.read_all().unwrap();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].ends_with("kept"));
+}
+
+#[test]
+fn filter_can_look_at_structured_data() {
+    let mut buffer = [0u8; 4096];
+    let target = BufferTarget::new("filter-sd", &mut buffer).unwrap();
+    target
+        .set_filter(|entry| entry.param_value("audit", "user").is_ok())
+        .unwrap();
+
+    let mut audited = entry("app", Severity::Info, "audited");
+    audited.add_new_element("audit").unwrap();
+    audited.add_new_param("audit", "user", "root").unwrap();
+    target.add_entry(&audited).unwrap();
+    target
+        .add_entry(&entry("app", Severity::Info, "not audited"))
+        .unwrap();
+
+    let messages = target.read_all().unwrap();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].ends_with("audited"));
+}
+
+#[test]
+fn mask_applies_before_filter() {
+    let mut buffer = [0u8; 4096];
+    let target = BufferTarget::new("filter-mask", &mut buffer).unwrap();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let filter_calls = Arc::clone(&calls);
+
+    target.set_mask(Severity::Warning.mask_upto()).unwrap();
+    target
+        .set_filter(move |_| {
+            filter_calls.fetch_add(1, Ordering::SeqCst);
+            true
+        })
+        .unwrap();
+
+    target
+        .add_entry(&entry("app", Severity::Debug, "masked"))
+        .unwrap();
+    target
+        .add_entry(&entry("app", Severity::Error, "logged"))
+        .unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(target.read_all().unwrap().len(), 1);
+}
+
+#[test]
+fn panicking_filter_drops_entry() {
+    let mut buffer = [0u8; 4096];
+    let target = BufferTarget::new("filter-panic", &mut buffer).unwrap();
+    target.set_filter(|_| panic!("filter failure")).unwrap();
+
+    target
+        .add_entry(&entry("app", Severity::Info, "dropped"))
+        .unwrap();
+
+    assert!(target.read_all().unwrap().is_empty());
+}
+
+#[test]
+fn cleared_filter_is_not_called() {
+    let mut buffer = [0u8; 4096];
+    let target = BufferTarget::new("filter-clear", &mut buffer).unwrap();
+    target.set_filter(|_| false).unwrap();
+    target.clear_filter().unwrap();
+
+    target
+        .add_entry(&entry("app", Severity::Info, "logged"))
+        .unwrap();
+
+    assert_eq!(target.read_all().unwrap().len(), 1);
+}
+
+#[test]
+fn filter_is_released_when_target_is_dropped() {
+    let captured = Arc::new(());
+    let filter_captured = Arc::clone(&captured);
+
+    let mut buffer = [0u8; 4096];
+    let target = BufferTarget::new("filter-drop", &mut buffer).unwrap();
+    target
+        .set_filter(move |_| Arc::strong_count(&filter_captured) > 0)
+        .unwrap();
+    assert_eq!(Arc::strong_count(&captured), 2);
+
+    drop(target);
+    assert_eq!(Arc::strong_count(&captured), 1);
+}
@@ -0,0 +1,191 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, UNIX_EPOCH};
+
+use stumpless::{Entry, ErrorKind, FunctionTarget, StumplessError, Target, TargetOption};
+use stumpless_sys::stumpless_target;
+
+fn rejecting_target(name: &str) -> FunctionTarget {
+    FunctionTarget::new(name, |entry| {
+        Err(StumplessError::new(
+            ErrorKind::InvalidId,
+            &format!("rejected {}", entry.message().unwrap().unwrap_or_default()),
+        ))
+    })
+    .unwrap()
+}
+
+// a target of its own that only provides the pointer, so it is logged to with
+// the default methods of the trait
+struct Wrapped(FunctionTarget);
+
+impl Target for Wrapped {
+    fn get_pointer(&self) -> *mut stumpless_target {
+        self.0.get_pointer()
+    }
+}
+
+#[test]
+fn closure_receives_entries() {
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let received = Arc::clone(&messages);
+    let target = FunctionTarget::new("receiving", move |entry| {
+        received.lock().unwrap().push(entry.message().unwrap());
+        Ok(())
+    })
+    .unwrap();
+
+    target
+        .add_entry(&Entry::builder().message("entry").build().unwrap())
+        .unwrap();
+    target.add_message("message").unwrap();
+    target.warn("log").unwrap();
+
+    assert_eq!(
+        *messages.lock().unwrap(),
+        [
+            Some(String::from("entry")),
+            Some(String::from("message")),
+            Some(String::from("log"))
+        ]
+    );
+}
+
+#[test]
+fn closure_errors_are_returned() {
+    let target = rejecting_target("rejecting");
+
+    let err = target.add_message("first").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidId);
+    assert_eq!(err.message(), "rejected first");
+
+    let err = target.warn("second").unwrap_err();
+    assert_eq!(err.message(), "rejected second");
+}
+
+#[test]
+fn closure_errors_are_returned_through_default_methods() {
+    let target = Wrapped(rejecting_target("wrapped"));
+
+    let err = target.add_message("wrapped").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidId);
+    assert_eq!(err.message(), "rejected wrapped");
+}
+
+#[test]
+fn closure_errors_are_not_left_behind() {
+    let wrapped = Wrapped(rejecting_target("left-behind"));
+    let accepting = FunctionTarget::new("accepting", |_| Ok(())).unwrap();
+    let rejecting = rejecting_target("fresh");
+
+    wrapped.add_message("stale").unwrap_err();
+    accepting.add_message("accepted").unwrap();
+
+    let err = rejecting.add_message("fresh").unwrap_err();
+    assert_eq!(err.message(), "rejected fresh");
+}
+
+#[test]
+fn panicking_closure_fails_the_call() {
+    let target = FunctionTarget::new("panicking", |_| panic!("closure panicked")).unwrap();
+
+    let err = target.add_message("panic").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::FunctionTargetFailure);
+
+    // the target can still be used afterwards
+    assert!(target.add_message("again").is_err());
+}
+
+#[test]
+fn formatted_closure_receives_messages() {
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let received = Arc::clone(&messages);
+    let target = FunctionTarget::formatted("formatted", move |message| {
+        received.lock().unwrap().push(message.to_vec());
+        Ok(())
+    })
+    .unwrap();
+
+    target.add_message("formatted message").unwrap();
+
+    let messages = messages.lock().unwrap();
+    let message = String::from_utf8_lossy(&messages[0]);
+    assert!(message.starts_with('<'), "{}", message);
+    assert!(message.ends_with(" formatted message"), "{}", message);
+}
+
+fn formatting_target(name: &str) -> (FunctionTarget, Arc<Mutex<Vec<String>>>) {
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let received = Arc::clone(&messages);
+    let target = FunctionTarget::formatted(name, move |message| {
+        received
+            .lock()
+            .unwrap()
+            .push(String::from_utf8(message.to_vec()).unwrap());
+        Ok(())
+    })
+    .unwrap();
+
+    (target, messages)
+}
+
+#[test]
+fn formatted_messages_use_target_options() {
+    let (target, messages) = formatting_target("formatted-pid");
+    target.set_option(TargetOption::Pid).unwrap();
+
+    target.add_message("with a pid").unwrap();
+    target.unset_option(TargetOption::Pid).unwrap();
+    target.add_message("without a pid").unwrap();
+
+    let messages = messages.lock().unwrap();
+    let procid = |message: &str| message.split(' ').nth(4).unwrap().to_string();
+    assert_eq!(procid(&messages[0]), std::process::id().to_string());
+    assert_eq!(procid(&messages[1]), "-");
+}
+
+#[test]
+fn formatted_messages_use_explicit_timestamp() {
+    let (target, messages) = formatting_target("formatted-timestamp");
+    let entry = Entry::builder()
+        .app_name("formatted")
+        .message("from the past")
+        .timestamp(UNIX_EPOCH + Duration::from_millis(1_700_000_000_123))
+        .build()
+        .unwrap();
+
+    target.add_entry(&entry).unwrap();
+
+    let messages = messages.lock().unwrap();
+    let fields: Vec<&str> = messages[0].splitn(3, ' ').collect();
+    assert_eq!(fields[1], "2023-11-14T22:13:20.123000Z");
+    assert!(messages[0].ends_with(" from the past"));
+}
+
+#[test]
+fn formatted_messages_can_be_large() {
+    let (target, messages) = formatting_target("formatted-large");
+    let large = "x".repeat(20_000);
+
+    target.add_message(&large).unwrap();
+    target.add_message("small again").unwrap();
+
+    let messages = messages.lock().unwrap();
+    assert!(messages[0].ends_with(&format!(" {}", large)));
+    assert!(messages[1].ends_with(" small again"));
+}
@@ -0,0 +1,155 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "tracing")]
+
+use stumpless::{Entry, Facility, Severity, StumplessLayer, Target};
+use tracing_subscriber::layer::SubscriberExt;
+
+mod common;
+use common::{entry_target, Entries};
+
+// runs the closure with a subscriber that sends events through the layer
+fn with_layer<T>(layer: StumplessLayer<T>, f: impl FnOnce())
+where
+    T: Target + Send + Sync + 'static,
+{
+    let subscriber = tracing_subscriber::registry().with(layer);
+    tracing::subscriber::with_default(subscriber, f);
+}
+
+fn single_entry(entries: &Entries) -> Entry {
+    let entries = entries.lock().unwrap();
+    assert_eq!(entries.len(), 1);
+    entries[0].try_clone().unwrap()
+}
+
+#[test]
+fn fields_are_captured() {
+    let (target, entries) = entry_target("layer-fields");
+    let layer = StumplessLayer::new(target)
+        .facility(Facility::Local1)
+        .app_name("layer-test");
+
+    with_layer(layer, || {
+        tracing::error!(user = "alice", attempts = 3, "login failed");
+    });
+
+    let entry = single_entry(&entries);
+    assert_eq!(
+        entry.prival(),
+        Facility::Local1 as i32 | Severity::Error as i32
+    );
+    assert_eq!(entry.app_name().unwrap(), "layer-test");
+    assert_eq!(entry.message().unwrap().unwrap(), "login failed");
+    assert_eq!(entry.element_names().unwrap(), ["fields@32473"]);
+    assert_eq!(entry.param_value("fields@32473", "user").unwrap(), "alice");
+    assert_eq!(entry.param_value("fields@32473", "attempts").unwrap(), "3");
+}
+
+#[test]
+fn levels_map_to_severities() {
+    let (target, entries) = entry_target("layer-levels");
+
+    with_layer(StumplessLayer::new(target), || {
+        tracing::error!("error");
+        tracing::warn!("warn");
+        tracing::info!("info");
+        tracing::debug!("debug");
+        tracing::trace!("trace");
+    });
+
+    let severities: Vec<i32> = entries
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|entry| entry.prival() & 0x07)
+        .collect();
+    assert_eq!(
+        severities,
+        [
+            Severity::Error as i32,
+            Severity::Warning as i32,
+            Severity::Info as i32,
+            Severity::Debug as i32,
+            Severity::Debug as i32,
+        ]
+    );
+}
+
+#[test]
+fn spans_are_captured_from_root() {
+    let (target, entries) = entry_target("layer-spans");
+
+    with_layer(StumplessLayer::new(target), || {
+        let request = tracing::error_span!("request", id = 7);
+        let _request = request.enter();
+        let handler = tracing::error_span!("handler", route = tracing::field::Empty);
+        handler.record("route", "/login");
+        let _handler = handler.enter();
+        tracing::error!("in a span");
+    });
+
+    let entry = single_entry(&entries);
+    assert_eq!(entry.msgid().unwrap(), "handler");
+    assert_eq!(
+        entry.element_names().unwrap(),
+        ["span.request@32473", "span.handler@32473"]
+    );
+    assert_eq!(entry.param_value("span.request@32473", "id").unwrap(), "7");
+    assert_eq!(
+        entry.param_value("span.handler@32473", "route").unwrap(),
+        "/login"
+    );
+}
+
+#[test]
+fn repeated_spans_are_added_once() {
+    let (target, entries) = entry_target("layer-repeated");
+
+    with_layer(StumplessLayer::new(target), || {
+        let outer = tracing::error_span!("retry", attempt = 1);
+        let _outer = outer.enter();
+        let inner = tracing::error_span!("retry", attempt = 2);
+        let _inner = inner.enter();
+        tracing::error!("retried");
+    });
+
+    let entry = single_entry(&entries);
+    assert_eq!(entry.element_names().unwrap(), ["span.retry@32473"]);
+    assert_eq!(
+        entry.param_value("span.retry@32473", "attempt").unwrap(),
+        "1"
+    );
+}
+
+#[test]
+fn enterprise_number_names_elements() {
+    let (target, entries) = entry_target("layer-pen");
+    let layer = StumplessLayer::new(target).enterprise_number(55555);
+
+    with_layer(layer, || {
+        let span = tracing::error_span!("job");
+        let _span = span.enter();
+        tracing::error!(queue = "mail", "started");
+    });
+
+    let entry = single_entry(&entries);
+    assert_eq!(
+        entry.element_names().unwrap(),
+        ["fields@55555", "span.job@55555"]
+    );
+}
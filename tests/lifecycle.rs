@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+
+use stumpless::{BufferTarget, DefaultTarget, ErrorKind, FileTarget, StreamTarget, Target};
+
+mod common;
+use common::{log_dir, log_path};
+
+#[test]
+fn flush_writes_file_messages() {
+    let dir = log_dir();
+    let path = log_path(&dir, "flush");
+    let target = FileTarget::new(&path).unwrap();
+
+    target.add_message("flushed").unwrap();
+    target.flush().unwrap();
+
+    assert!(fs::read_to_string(&path).unwrap().contains("flushed"));
+}
+
+#[test]
+fn reopen_follows_rotated_file() {
+    let dir = log_dir();
+    let path = log_path(&dir, "rotate");
+    let rotated = log_path(&dir, "rotate.1");
+    let target = FileTarget::new(&path).unwrap();
+
+    target.add_message("before rotation").unwrap();
+    fs::rename(&path, &rotated).unwrap();
+    target.reopen().unwrap();
+    target.add_message("after rotation").unwrap();
+    target.close().unwrap();
+
+    let old = fs::read_to_string(&rotated).unwrap();
+    assert!(old.contains("before rotation"));
+    assert!(!old.contains("after rotation"));
+
+    let new = fs::read_to_string(&path).unwrap();
+    assert!(new.contains("after rotation"));
+    assert!(!new.contains("before rotation"));
+}
+
+#[test]
+fn failed_reopen_keeps_old_file() {
+    let dir = log_dir();
+    let subdir = dir.path().join("logs");
+    fs::create_dir(&subdir).unwrap();
+    let path = subdir.join("reopen.log");
+    let target = FileTarget::new(path.to_str().unwrap()).unwrap();
+
+    let moved = dir.path().join("moved");
+    fs::rename(&subdir, &moved).unwrap();
+    let err = target.reopen().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Io);
+
+    target.add_message("still open").unwrap();
+    target.close().unwrap();
+    let contents = fs::read_to_string(moved.join("reopen.log")).unwrap();
+    assert!(contents.contains("still open"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn close_reports_write_errors() {
+    let target = FileTarget::new("/dev/full").unwrap();
+
+    target.add_message("no space").unwrap();
+    let err = target.close().unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Io);
+    assert_eq!(err.code(), Some(28));
+}
+
+#[test]
+fn close_writes_file_messages() {
+    let dir = log_dir();
+    let path = log_path(&dir, "close");
+    let target = FileTarget::new(&path).unwrap();
+
+    target.add_message("closed").unwrap();
+    target.close().unwrap();
+
+    assert!(fs::read_to_string(&path).unwrap().contains("closed"));
+}
+
+#[test]
+fn unbuffered_targets_flush_and_reopen() {
+    let mut buffer = [0u8; 1024];
+    let target = BufferTarget::new("lifecycle", &mut buffer).unwrap();
+
+    target.flush().unwrap();
+    target.reopen().unwrap();
+    target.close().unwrap();
+}
+
+#[test]
+fn stream_targets_flush_and_close() {
+    let target = StreamTarget::stderr("lifecycle").unwrap();
+
+    target.add_message("stream lifecycle").unwrap();
+    target.flush().unwrap();
+    target.reopen().unwrap();
+    target.close().unwrap();
+}
+
+#[test]
+fn default_target_stays_open() {
+    let target = DefaultTarget::get_default_target().unwrap();
+    let pointer = target.get_pointer();
+    target.close().unwrap();
+
+    let target = DefaultTarget::get_default_target().unwrap();
+    assert_eq!(target.get_pointer(), pointer);
+}
@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "log")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use log::{Level, LevelFilter, Log, Record};
+use stumpless::{
+    ErrorKind, Facility, FunctionTarget, Severity, StumplessError, StumplessLogger, Target,
+};
+use stumpless_sys::stumpless_target;
+
+mod common;
+use common::entry_target;
+
+fn record(level: Level, message: &str, logger: &impl Log) {
+    logger.log(
+        &Record::builder()
+            .level(level)
+            .args(format_args!("{}", message))
+            .module_path(Some("logger::tests"))
+            .file(Some("tests/logger.rs"))
+            .line(Some(42))
+            .build(),
+    );
+}
+
+#[test]
+fn levels_map_to_severities() {
+    let (target, entries) = entry_target("logger-levels");
+    let logger = StumplessLogger::new(target).level(LevelFilter::Trace);
+
+    for level in [
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ] {
+        record(level, level.as_str(), &logger);
+    }
+
+    let severities: Vec<i32> = entries
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|entry| entry.prival() & 0x07)
+        .collect();
+    assert_eq!(
+        severities,
+        [
+            Severity::Error as i32,
+            Severity::Warning as i32,
+            Severity::Info as i32,
+            Severity::Debug as i32,
+            Severity::Debug as i32,
+        ]
+    );
+}
+
+#[test]
+fn records_below_level_are_dropped() {
+    let (target, entries) = entry_target("logger-filtered");
+    let logger = StumplessLogger::new(target).level(LevelFilter::Warn);
+
+    record(Level::Info, "dropped", &logger);
+    record(Level::Warn, "logged", &logger);
+
+    let entries = entries.lock().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].message().unwrap().unwrap(), "logged");
+}
+
+#[test]
+fn source_is_added_as_log_element() {
+    let (target, entries) = entry_target("logger-source");
+    let logger = StumplessLogger::new(target)
+        .facility(Facility::Local2)
+        .app_name("logger-test");
+
+    record(Level::Error, "with a source", &logger);
+
+    let entries = entries.lock().unwrap();
+    let entry = &entries[0];
+    assert_eq!(
+        entry.prival(),
+        Facility::Local2 as i32 | Severity::Error as i32
+    );
+    assert_eq!(entry.app_name().unwrap(), "logger-test");
+    assert_eq!(
+        entry.param_value("log@32473", "module").unwrap(),
+        "logger::tests"
+    );
+    assert_eq!(
+        entry.param_value("log@32473", "file").unwrap(),
+        "tests/logger.rs"
+    );
+    assert_eq!(entry.param_value("log@32473", "line").unwrap(), "42");
+}
+
+#[test]
+fn enterprise_number_names_log_element() {
+    let (target, entries) = entry_target("logger-pen");
+    let logger = StumplessLogger::new(target).enterprise_number(55555);
+
+    record(Level::Error, "with a pen", &logger);
+
+    let entries = entries.lock().unwrap();
+    assert_eq!(entries[0].element_names().unwrap(), ["log@55555"]);
+}
+
+struct FlushCounting {
+    target: FunctionTarget,
+    flushes: Arc<AtomicUsize>,
+}
+
+impl Target for FlushCounting {
+    fn get_pointer(&self) -> *mut stumpless_target {
+        self.target.get_pointer()
+    }
+
+    fn flush(&self) -> Result<(), StumplessError> {
+        self.flushes.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[test]
+fn flush_flushes_target() {
+    let (target, _) = entry_target("logger-flush");
+    let flushes = Arc::new(AtomicUsize::new(0));
+    let logger = StumplessLogger::new(FlushCounting {
+        target,
+        flushes: Arc::clone(&flushes),
+    });
+
+    logger.flush();
+
+    assert_eq!(flushes.load(Ordering::SeqCst), 1);
+}
+
+// the global logger can only be installed once, so this is the only test that
+// installs one
+#[test]
+fn init_installs_global_logger() {
+    let (target, entries) = entry_target("logger-init");
+    StumplessLogger::new(target)
+        .level(LevelFilter::Warn)
+        .init()
+        .unwrap();
+
+    assert_eq!(log::max_level(), LevelFilter::Warn);
+    log::info!("dropped");
+    log::warn!("from the log crate");
+
+    let entries = entries.lock().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].message().unwrap().unwrap(), "from the log crate");
+    assert!(entries[0].has_element("log@32473"));
+
+    let (other_target, _) = entry_target("logger-second-init");
+    let err = StumplessLogger::new(other_target).init().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::LoggerInitFailure);
+}
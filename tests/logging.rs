@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use stumpless::{level_enabled, stump, BufferTarget, Severity, MAX_LEVEL};
+
+const SEVERITIES: [Severity; 8] = [
+    Severity::Emergency,
+    Severity::Alert,
+    Severity::Critical,
+    Severity::Error,
+    Severity::Warning,
+    Severity::Notice,
+    Severity::Info,
+    Severity::Debug,
+];
+
+#[test]
+fn levels_below_max_level_are_skipped() {
+    let mut buffer = [0u8; 4096];
+    let target = BufferTarget::new("log-max-level", &mut buffer).unwrap();
+
+    for severity in SEVERITIES {
+        stump!(target, severity, "{}", severity).unwrap();
+    }
+
+    let messages = target.read_all().unwrap();
+    let enabled: Vec<&Severity> = SEVERITIES.iter().filter(|s| level_enabled(**s)).collect();
+    assert_eq!(messages.len(), enabled.len());
+    assert!(messages.last().unwrap().ends_with(MAX_LEVEL.name()));
+}
+
+// these need every level to be enabled, which is the default
+#[cfg(not(any(
+    feature = "max-level-emerg",
+    feature = "max-level-alert",
+    feature = "max-level-crit",
+    feature = "max-level-err",
+    feature = "max-level-warning",
+    feature = "max-level-notice",
+    feature = "max-level-info"
+)))]
+mod all_levels {
+    use stumpless::{stump, stump_err, stump_info, BufferTarget, Facility, Severity, Target};
+
+    #[test]
+    fn log_uses_target_defaults() {
+        let mut buffer = [0u8; 4096];
+        let target = BufferTarget::new("log-defaults", &mut buffer).unwrap();
+        target.set_default_app_name("app").unwrap();
+        target.set_default_msgid("MSG").unwrap();
+        target.set_default_facility(Facility::Local0).unwrap();
+
+        target.log(Severity::Notice, "noticed").unwrap();
+
+        let message = target.read().unwrap().unwrap();
+        assert!(message.starts_with("<133>1 "));
+        assert!(message.contains(" app - MSG - noticed"));
+    }
+
+    #[test]
+    fn level_helpers_set_severity() {
+        let mut buffer = [0u8; 4096];
+        let target = BufferTarget::new("log-levels", &mut buffer).unwrap();
+
+        target.err("failed").unwrap();
+        target.warn("careful").unwrap();
+        target.debug("details").unwrap();
+
+        let messages = target.read_all().unwrap();
+        assert!(messages[0].starts_with("<11>1 "));
+        assert!(messages[1].starts_with("<12>1 "));
+        assert!(messages[2].starts_with("<15>1 "));
+    }
+
+    #[test]
+    fn macros_format_messages() {
+        let mut buffer = [0u8; 4096];
+        let target = BufferTarget::new("log-macros", &mut buffer).unwrap();
+        let id = 42;
+
+        stump_info!(target, "user {} logged in", id).unwrap();
+        stump_err!(&target, "{} failures", 3).unwrap();
+        stump!(target, Severity::Alert, "{id} alerted").unwrap();
+
+        let messages = target.read_all().unwrap();
+        assert!(messages[0].starts_with("<14>1 "));
+        assert!(messages[0].ends_with("user 42 logged in"));
+        assert!(messages[1].starts_with("<11>1 "));
+        assert!(messages[1].ends_with("3 failures"));
+        assert!(messages[2].starts_with("<9>1 "));
+        assert!(messages[2].ends_with("42 alerted"));
+    }
+}
@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::process::{Command, Output};
+
+use stumpless::{Severity, Target};
+
+mod common;
+use common::{entry, log_dir, log_path, recording_target};
+
+fn log_to_file(path: &str, priority: &str, min_severities: &[&str]) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_stumpless"));
+    command.args(["--log-file", path, "--priority", priority]);
+    for min_severity in min_severities {
+        command.args(["--min-severity", min_severity]);
+    }
+
+    command.arg("masked message").output().unwrap()
+}
+
+fn logged(path: &str) -> bool {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .contains("masked message")
+}
+
+#[test]
+fn mask_round_trips() {
+    let (target, _) = recording_target("round-trip");
+
+    target.set_mask(Severity::Warning.mask_upto()).unwrap();
+    assert_eq!(target.get_mask(), Severity::Warning.mask_upto());
+
+    let mask = Severity::Error.mask() | Severity::Debug.mask();
+    target.set_mask(mask).unwrap();
+    assert_eq!(target.get_mask(), mask);
+}
+
+#[test]
+fn entries_below_mask_are_dropped() {
+    let (target, messages) = recording_target("mask");
+    target.set_mask(Severity::Warning.mask_upto()).unwrap();
+
+    target
+        .add_entry(&entry("mask", Severity::Info, "dropped"))
+        .unwrap();
+    target
+        .add_entry(&entry("mask", Severity::Warning, "at minimum"))
+        .unwrap();
+    target
+        .add_entry(&entry("mask", Severity::Critical, "above minimum"))
+        .unwrap();
+
+    assert_eq!(
+        messages.lock().unwrap()[..],
+        ["at minimum", "above minimum"]
+    );
+}
+
+#[test]
+fn min_severity_applies_to_all_targets() {
+    let dir = log_dir();
+
+    let path = log_path(&dir, "below");
+    assert!(log_to_file(&path, "user.info", &["err"]).status.success());
+    assert!(!logged(&path));
+
+    let path = log_path(&dir, "above");
+    assert!(log_to_file(&path, "user.crit", &["3"]).status.success());
+    assert!(logged(&path));
+}
+
+#[test]
+fn min_severity_for_target_takes_precedence() {
+    let dir = log_dir();
+
+    let path = log_path(&dir, "target");
+    let output = log_to_file(&path, "user.info", &["err", "log-file=debug"]);
+    assert!(output.status.success());
+    assert!(logged(&path));
+
+    let path = log_path(&dir, "other-target");
+    let output = log_to_file(&path, "user.info", &["stdout=debug", "err"]);
+    assert!(output.status.success());
+    assert!(!logged(&path));
+}
+
+#[test]
+fn min_severity_rejects_unknown_target() {
+    let dir = log_dir();
+    let path = log_path(&dir, "unknown");
+
+    let output = log_to_file(&path, "user.info", &["log-files=debug"]);
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown target 'log-files'"));
+    assert!(!logged(&path));
+}
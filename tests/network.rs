@@ -0,0 +1,214 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "network")]
+
+use std::io::Read;
+use std::net::{TcpListener, UdpSocket};
+
+use stumpless::{
+    ErrorKind, Framing, NetworkProtocol, NetworkTarget, Target, TargetOption, TransportProtocol,
+};
+
+#[test]
+fn addresses_are_split() {
+    let split = NetworkTarget::split_address;
+
+    assert_eq!(split("example.com").unwrap(), ("example.com", None));
+    assert_eq!(
+        split("example.com:601").unwrap(),
+        ("example.com", Some("601"))
+    );
+    assert_eq!(
+        split("127.0.0.1:6514").unwrap(),
+        ("127.0.0.1", Some("6514"))
+    );
+    assert_eq!(split("::1").unwrap(), ("::1", None));
+    assert_eq!(split("[::1]").unwrap(), ("::1", None));
+    assert_eq!(
+        split("[2001:db8::1]:601").unwrap(),
+        ("2001:db8::1", Some("601"))
+    );
+}
+
+#[test]
+fn invalid_addresses_are_rejected() {
+    for address in [
+        "",
+        ":601",
+        "example.com:",
+        "[::1",
+        "[::1]601",
+        "[::1]:",
+        "[]:601",
+    ] {
+        let err = NetworkTarget::split_address(address).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidAddress, "{}", address);
+    }
+}
+
+#[test]
+fn builder_uses_port_from_address() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    let target = NetworkTarget::builder(&address)
+        .transport(TransportProtocol::Tcp)
+        .port("1")
+        .build()
+        .unwrap();
+    assert_eq!(
+        target.get_transport_port().unwrap(),
+        address.rsplit(':').next().unwrap()
+    );
+    target.add_message("over tcp").unwrap();
+    drop(target);
+
+    let mut received = String::new();
+    let (mut stream, _) = listener.accept().unwrap();
+    stream.read_to_string(&mut received).unwrap();
+    assert!(received.ends_with("over tcp"));
+}
+
+#[test]
+fn builder_uses_given_port() {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let port = socket.local_addr().unwrap().port().to_string();
+
+    let target = NetworkTarget::builder("127.0.0.1")
+        .network(NetworkProtocol::Ipv4)
+        .transport(TransportProtocol::Udp)
+        .port(&port)
+        .build()
+        .unwrap();
+    target.add_message("over udp").unwrap();
+
+    let mut buffer = [0u8; 1024];
+    let size = socket.recv(&mut buffer).unwrap();
+    assert!(String::from_utf8_lossy(&buffer[..size]).ends_with("over udp"));
+}
+
+// logs the messages to a new TCP target and returns everything it sent
+fn send_over_tcp(framing: Framing, messages: &[&str]) -> Vec<u8> {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    let target = NetworkTarget::builder(&address)
+        .transport(TransportProtocol::Tcp)
+        .framing(framing)
+        .build()
+        .unwrap();
+    for message in messages {
+        target.add_message(message).unwrap();
+    }
+    drop(target);
+
+    let mut received = Vec::new();
+    let (mut stream, _) = listener.accept().unwrap();
+    stream.read_to_end(&mut received).unwrap();
+    received
+}
+
+fn octet_counted_frames(mut received: &[u8]) -> Vec<String> {
+    let mut frames = Vec::new();
+
+    while !received.is_empty() {
+        let space = received.iter().position(|&b| b == b' ').unwrap();
+        let length: usize = std::str::from_utf8(&received[..space])
+            .unwrap()
+            .parse()
+            .unwrap();
+        let frame = &received[space + 1..space + 1 + length];
+        frames.push(String::from_utf8(frame.to_vec()).unwrap());
+        received = &received[space + 1 + length..];
+    }
+
+    frames
+}
+
+#[test]
+fn octet_counting_keeps_multiline_messages() {
+    let received = send_over_tcp(Framing::OctetCounting, &["first", "line one\nline two"]);
+
+    let frames = octet_counted_frames(&received);
+    assert_eq!(frames.len(), 2);
+    assert!(frames[0].ends_with(" first"));
+    assert!(frames[1].ends_with(" line one\nline two"));
+}
+
+#[test]
+fn non_transparent_framing_escapes_newlines() {
+    let received = send_over_tcp(Framing::NonTransparent, &["first", "line one\nline two"]);
+
+    let received = String::from_utf8(received).unwrap();
+    let frames: Vec<&str> = received.split_terminator('\n').collect();
+    assert!(received.ends_with('\n'));
+    assert_eq!(frames.len(), 2);
+    assert!(frames[0].starts_with("<14>1 "));
+    assert!(frames[0].ends_with(" first"));
+    assert!(frames[1].ends_with(" line one#012line two"));
+}
+
+#[test]
+fn non_transparent_framing_reconnects_on_reopen() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port().to_string();
+
+    let target = NetworkTarget::builder("127.0.0.1")
+        .transport(TransportProtocol::Tcp)
+        .framing(Framing::NonTransparent)
+        .port(&port)
+        .build()
+        .unwrap();
+    assert_eq!(target.get_transport_port().unwrap(), port);
+
+    let (mut first_stream, _) = listener.accept().unwrap();
+    target.add_message("before").unwrap();
+    target.reopen().unwrap();
+    target.add_message("after").unwrap();
+    drop(target);
+
+    let (mut second_stream, _) = listener.accept().unwrap();
+    let mut first = String::new();
+    let mut second = String::new();
+    first_stream.read_to_string(&mut first).unwrap();
+    second_stream.read_to_string(&mut second).unwrap();
+    assert!(first.ends_with(" before\n"));
+    assert!(second.ends_with(" after\n"));
+}
+
+#[test]
+fn framed_messages_use_target_options() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    let target = NetworkTarget::builder(&address)
+        .transport(TransportProtocol::Tcp)
+        .framing(Framing::NonTransparent)
+        .build()
+        .unwrap();
+    target.set_default_app_name("framed-app").unwrap();
+    target.set_option(TargetOption::Pid).unwrap();
+    target.add_message("with options").unwrap();
+    drop(target);
+
+    let mut received = String::new();
+    let (mut stream, _) = listener.accept().unwrap();
+    stream.read_to_string(&mut received).unwrap();
+    let fields: Vec<&str> = received.splitn(8, ' ').collect();
+    assert_eq!(fields[3], "framed-app");
+    assert_eq!(fields[4], std::process::id().to_string());
+}
@@ -0,0 +1,108 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use stumpless::{ErrorKind, Facility, Prival, Severity};
+
+fn prival(priority: &str) -> Prival {
+    priority.parse().unwrap()
+}
+
+#[test]
+fn numbers_parse() {
+    assert_eq!(prival("0").value(), 0);
+    assert_eq!(prival("134").value(), 134);
+    assert_eq!(prival("191").value(), 191);
+    assert_eq!(prival("<134>").value(), 134);
+    assert_eq!(prival(" 14 ").value(), 14);
+}
+
+#[test]
+fn names_and_codes_parse() {
+    let local0_info = Prival::new(Facility::Local0, Severity::Info);
+
+    assert_eq!(prival("local0.info"), local0_info);
+    assert_eq!(prival("LOCAL0.INFO"), local0_info);
+    assert_eq!(prival("16.6"), local0_info);
+    assert_eq!(prival("local0.6"), local0_info);
+    assert_eq!(prival("16.info"), local0_info);
+    assert_eq!(
+        prival("authpriv.warn"),
+        Prival::new(Facility::Auth2, Severity::Warning)
+    );
+}
+
+#[test]
+fn severity_alone_uses_user_facility() {
+    assert_eq!(prival("err"), Prival::new(Facility::User, Severity::Error));
+    assert_eq!(prival("debug").value(), 15);
+}
+
+#[test]
+fn prival_splits_into_facility_and_severity() {
+    let parsed = prival("<165>");
+
+    assert_eq!(parsed.facility(), Facility::Local4);
+    assert_eq!(parsed.severity(), Severity::Notice);
+    assert_eq!(parsed.to_string(), "local4.notice");
+    assert_eq!(Prival::from((Facility::Local4, Severity::Notice)), parsed);
+}
+
+#[test]
+fn numeric_conversions_check_range() {
+    assert_eq!(i32::from(Prival::try_from(191u8).unwrap()), 191);
+    assert_eq!(u8::from(Prival::try_from(5i32).unwrap()), 5);
+    assert_eq!(
+        Prival::try_from(192u8).unwrap_err().kind(),
+        ErrorKind::InvalidPrival
+    );
+    assert_eq!(
+        Prival::try_from(-1i32).unwrap_err().kind(),
+        ErrorKind::InvalidPrival
+    );
+}
+
+#[test]
+fn out_of_range_numbers_are_rejected() {
+    for priority in ["192", "<192>", "256", "<>", "<134", ""] {
+        let err = priority.parse::<Prival>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidPrival, "{:?}", priority);
+    }
+}
+
+#[test]
+fn signs_are_rejected() {
+    for priority in ["+5", "<+5>", "-5", "<-5>"] {
+        let err = priority.parse::<Prival>().unwrap_err();
+        assert!(err.message().contains(priority), "{}", err);
+    }
+
+    assert!("local0.+6".parse::<Prival>().is_err());
+    assert!("+16.info".parse::<Prival>().is_err());
+}
+
+#[test]
+fn errors_name_the_bad_token() {
+    let err = "local9.info".parse::<Prival>().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidFacility);
+    assert!(err.message().contains("'local9'"), "{}", err);
+
+    let err = "local0.loud".parse::<Prival>().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidSeverity);
+    assert!(err.message().contains("'loud'"), "{}", err);
+
+    let err = "local0.8".parse::<Prival>().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidSeverity);
+}
@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use stumpless::{ErrorKind, Severity};
+
+#[test]
+fn names_parse_ignoring_case() {
+    assert_eq!("emerg".parse::<Severity>().unwrap(), Severity::Emergency);
+    assert_eq!("INFO".parse::<Severity>().unwrap(), Severity::Info);
+    assert_eq!("Debug".parse::<Severity>().unwrap(), Severity::Debug);
+}
+
+#[test]
+fn aliases_parse() {
+    let aliases = [
+        ("panic", Severity::Emergency),
+        ("err", Severity::Error),
+        ("error", Severity::Error),
+        ("warn", Severity::Warning),
+        ("warning", Severity::Warning),
+    ];
+
+    for (alias, severity) in aliases {
+        assert_eq!(alias.parse::<Severity>().unwrap(), severity, "{}", alias);
+    }
+}
+
+#[test]
+fn unknown_names_are_rejected() {
+    for name in ["", "fatal", "critical", "info ", "3"] {
+        let err = name.parse::<Severity>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidSeverity, "{:?}", name);
+    }
+}
+
+#[test]
+fn display_uses_canonical_names() {
+    assert_eq!("error".parse::<Severity>().unwrap().to_string(), "err");
+    assert_eq!("warn".parse::<Severity>().unwrap().to_string(), "warning");
+    assert_eq!("panic".parse::<Severity>().unwrap().to_string(), "emerg");
+}
+
+#[test]
+fn every_code_round_trips() {
+    for code in 0..=7u8 {
+        let severity = Severity::try_from(code).unwrap();
+        assert_eq!(severity.code(), code);
+        assert_eq!(u8::from(severity), code);
+        assert_eq!(severity.name().parse::<Severity>().unwrap(), severity);
+    }
+
+    assert_eq!(
+        Severity::try_from(8).unwrap_err().kind(),
+        ErrorKind::InvalidSeverity
+    );
+}
+
+#[test]
+fn more_severe_is_greater() {
+    assert!(Severity::Emergency > Severity::Alert);
+    assert!(Severity::Error > Severity::Warning);
+    assert!(Severity::Info > Severity::Debug);
+
+    let mut severities = vec![Severity::Info, Severity::Critical, Severity::Debug];
+    severities.sort();
+    assert_eq!(
+        severities,
+        [Severity::Debug, Severity::Info, Severity::Critical]
+    );
+}
+
+#[test]
+fn masks_follow_codes() {
+    assert_eq!(Severity::Emergency.mask(), 0x01);
+    assert_eq!(Severity::Debug.mask(), 0x80);
+    assert_eq!(Severity::Warning.mask_upto(), 0x1f);
+    assert_eq!(Severity::Debug.mask_upto(), 0xff);
+}
@@ -0,0 +1,185 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "sqlite")]
+
+use std::process::Command;
+use std::time::{Duration, UNIX_EPOCH};
+
+use rusqlite::Connection;
+use stumpless::{Entry, ErrorKind, Facility, Severity, Sqlite3Target, Target};
+use tempfile::TempDir;
+
+fn database() -> (TempDir, String) {
+    let dir = tempfile::tempdir().unwrap();
+    let db_filename = dir.path().join("logs.sqlite3");
+    (dir, db_filename.to_str().unwrap().to_string())
+}
+
+#[test]
+fn entries_are_inserted_into_the_default_table() {
+    let (_dir, db_filename) = database();
+    let target = Sqlite3Target::new(&db_filename).unwrap();
+    target.create_default_table().unwrap();
+
+    let mut entry = Entry::builder()
+        .facility(Facility::Local1)
+        .severity(Severity::Warning)
+        .app_name("app")
+        .hostname("host")
+        .procid("42")
+        .msgid("msgid")
+        .message("inserted")
+        .timestamp(UNIX_EPOCH + Duration::from_secs(1))
+        .build()
+        .unwrap();
+    entry
+        .add_new_element("element")
+        .unwrap()
+        .add_new_param("element", "name", "value")
+        .unwrap();
+    target.add_entry(&entry).unwrap();
+
+    let connection = Connection::open(&db_filename).unwrap();
+    let row = connection
+        .query_row(
+            "SELECT prival, version, timestamp, hostname, app_name, procid, msgid, \
+             structured_data, message FROM logs",
+            [],
+            |row| {
+                Ok((
+                    row.get::<_, i32>(0)?,
+                    row.get::<_, i32>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, String>(6)?,
+                    row.get::<_, String>(7)?,
+                    row.get::<_, String>(8)?,
+                ))
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        row,
+        (
+            140,
+            1,
+            String::from("1970-01-01T00:00:01.000000Z"),
+            String::from("host"),
+            String::from("app"),
+            String::from("42"),
+            String::from("msgid"),
+            String::from("[element name=\"value\"]"),
+            String::from("inserted"),
+        )
+    );
+}
+
+#[test]
+fn missing_table_is_an_error() {
+    let (_dir, db_filename) = database();
+    let target = Sqlite3Target::new(&db_filename).unwrap();
+
+    let err = target.err("no table").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Sqlite3Failure);
+    assert_eq!(err.code_type(), Some("sqlite3 result code"));
+    assert!(err.message().contains("logs"), "{}", err);
+}
+
+#[test]
+fn custom_insert_sql_is_used() {
+    let (_dir, db_filename) = database();
+    let connection = Connection::open(&db_filename).unwrap();
+    connection
+        .execute_batch("CREATE TABLE custom ( facility INTEGER, severity INTEGER, message TEXT )")
+        .unwrap();
+
+    let target = Sqlite3Target::new(&db_filename).unwrap();
+    target
+        .set_insert_sql(
+            "INSERT INTO custom ( facility, severity, message ) \
+             VALUES ( $facility, $severity, $message )",
+        )
+        .unwrap();
+    target.set_default_facility(Facility::Daemon).unwrap();
+    target.err("custom").unwrap();
+    target.warn("second").unwrap();
+
+    let mut select = connection
+        .prepare("SELECT facility, severity, message FROM custom ORDER BY rowid")
+        .unwrap();
+    let rows = select
+        .query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get::<_, String>(2)?))
+        })
+        .unwrap()
+        .collect::<Result<Vec<(u8, u8, String)>, _>>()
+        .unwrap();
+
+    assert_eq!(
+        rows,
+        [
+            (3, 3, String::from("custom")),
+            (3, 4, String::from("second"))
+        ]
+    );
+}
+
+#[test]
+fn invalid_insert_sql_is_rejected() {
+    let (_dir, db_filename) = database();
+    let target = Sqlite3Target::new(&db_filename).unwrap();
+    target.create_default_table().unwrap();
+
+    let err = target
+        .set_insert_sql("INSERT INTO missing ( message ) VALUES ( $message )")
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::Sqlite3Failure);
+
+    // the previous statement is still used
+    target.err("still logged").unwrap();
+    let connection = Connection::open(&db_filename).unwrap();
+    let count: i32 = connection
+        .query_row("SELECT COUNT(*) FROM logs", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn sqlite_option_inserts_the_message() {
+    let (_dir, db_filename) = database();
+
+    let status = Command::new(env!("CARGO_BIN_EXE_stumpless"))
+        .args(["--sqlite", &db_filename, "--priority", "local2.crit"])
+        .args(["logged", "from", "the", "cli"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let connection = Connection::open(&db_filename).unwrap();
+    let (prival, message): (i32, String) = connection
+        .query_row("SELECT prival, message FROM logs", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(prival, 146);
+    assert_eq!(message, "logged from the cli");
+}
@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use stumpless::{BufferTarget, Facility, Severity, Target, TargetOption};
+
+fn fields(message: &str) -> Vec<&str> {
+    message.splitn(8, ' ').collect()
+}
+
+#[test]
+fn default_app_name_is_used_for_messages() {
+    let mut buffer = [0u8; 1024];
+    let target = BufferTarget::new("defaults-app-name", &mut buffer).unwrap();
+
+    target.set_default_app_name("billing").unwrap();
+    target.add_message("with an app name").unwrap();
+
+    assert_eq!(target.get_default_app_name().unwrap(), "billing");
+    let message = target.read().unwrap().unwrap();
+    assert_eq!(fields(&message)[3], "billing");
+}
+
+#[test]
+fn default_msgid_is_used_for_messages() {
+    let mut buffer = [0u8; 1024];
+    let target = BufferTarget::new("defaults-msgid", &mut buffer).unwrap();
+
+    target.set_default_msgid("invoice").unwrap();
+    target.add_message("with a msgid").unwrap();
+
+    assert_eq!(target.get_default_msgid().unwrap(), "invoice");
+    let message = target.read().unwrap().unwrap();
+    assert_eq!(fields(&message)[5], "invoice");
+}
+
+#[test]
+fn default_facility_is_used_for_messages() {
+    let mut buffer = [0u8; 1024];
+    let target = BufferTarget::new("defaults-facility", &mut buffer).unwrap();
+
+    target.set_default_facility(Facility::Local4).unwrap();
+    target.log(Severity::Error, "with a facility").unwrap();
+
+    assert_eq!(target.get_default_facility().unwrap(), Facility::Local4);
+    let message = target.read().unwrap().unwrap();
+    let prival = Facility::Local4 as i32 | Severity::Error as i32;
+    assert!(message.starts_with(&format!("<{}>1 ", prival)));
+}
+
+#[test]
+fn options_are_set_and_unset() {
+    let mut buffer = [0u8; 1024];
+    let target = BufferTarget::new("defaults-options", &mut buffer).unwrap();
+
+    assert!(!target.get_option(TargetOption::Pid));
+    target.set_option(TargetOption::Pid).unwrap();
+    target.set_option(TargetOption::Ndelay).unwrap();
+    assert!(target.get_option(TargetOption::Pid));
+    assert!(target.get_option(TargetOption::Ndelay));
+
+    target.add_message("with a pid").unwrap();
+    let message = target.read().unwrap().unwrap();
+    assert_eq!(fields(&message)[4], std::process::id().to_string());
+
+    target.unset_option(TargetOption::Pid).unwrap();
+    assert!(!target.get_option(TargetOption::Pid));
+    assert!(target.get_option(TargetOption::Ndelay));
+
+    target.add_message("without a pid").unwrap();
+    let message = target.read().unwrap().unwrap();
+    assert_eq!(fields(&message)[4], "-");
+}
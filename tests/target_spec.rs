@@ -0,0 +1,180 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+
+use stumpless::{ErrorKind, Framing, NetworkProtocol, TargetSpec, TransportProtocol};
+
+mod common;
+use common::{log_dir, log_path};
+
+fn parse(spec: &str) -> TargetSpec {
+    spec.parse().unwrap()
+}
+
+fn network(
+    network: NetworkProtocol,
+    transport: TransportProtocol,
+    host: &str,
+    port: Option<&str>,
+) -> TargetSpec {
+    TargetSpec::Network {
+        network,
+        transport,
+        host: host.to_string(),
+        port: port.map(str::to_string),
+        framing: None,
+    }
+}
+
+#[test]
+fn local_specs_are_parsed() {
+    assert_eq!(parse("stdout:"), TargetSpec::Stdout);
+    assert_eq!(parse("stderr:"), TargetSpec::Stderr);
+    assert_eq!(parse("default:"), TargetSpec::Default);
+    assert_eq!(parse("journald://"), TargetSpec::Journald);
+    assert_eq!(
+        parse("file:///var/log/app.log"),
+        TargetSpec::File("/var/log/app.log".to_string())
+    );
+    assert_eq!(
+        parse("file:app.log"),
+        TargetSpec::File("app.log".to_string())
+    );
+    assert_eq!(
+        parse("unix:///dev/log"),
+        TargetSpec::Socket("/dev/log".to_string())
+    );
+    assert_eq!(
+        parse("sqlite://localhost/var/log/app.db"),
+        TargetSpec::Sqlite("/var/log/app.db".to_string())
+    );
+    assert_eq!(
+        parse("wel://Application"),
+        TargetSpec::WindowsEventLog("Application".to_string())
+    );
+}
+
+#[test]
+fn network_specs_are_parsed() {
+    use NetworkProtocol::{Ipv4, Ipv6};
+    use TransportProtocol::{Tcp, Udp};
+
+    assert_eq!(
+        parse("udp://host:514"),
+        network(Ipv4, Udp, "host", Some("514"))
+    );
+    assert_eq!(parse("tcp://host"), network(Ipv4, Tcp, "host", None));
+    assert_eq!(
+        parse("tcp6://[::1]:601"),
+        network(Ipv6, Tcp, "::1", Some("601"))
+    );
+    assert_eq!(parse("udp6://::1"), network(Ipv6, Udp, "::1", None));
+    assert_eq!(
+        parse("udp://[2001:db8::1]:514/"),
+        network(Ipv6, Udp, "2001:db8::1", Some("514"))
+    );
+    assert_eq!(
+        parse("TCP4://10.0.0.1:6514"),
+        network(Ipv4, Tcp, "10.0.0.1", Some("6514"))
+    );
+}
+
+#[test]
+fn framing_is_parsed_for_tcp() {
+    let spec = parse("tcp://host:601?framing=non-transparent");
+    match spec {
+        TargetSpec::Network { framing, port, .. } => {
+            assert_eq!(framing, Some(Framing::NonTransparent));
+            assert_eq!(port.as_deref(), Some("601"));
+        }
+        _ => panic!("expected a network spec"),
+    }
+}
+
+#[test]
+fn invalid_specs_are_rejected() {
+    let invalid = [
+        "",
+        "/var/log/app.log",
+        "ftp://host",
+        "stdout:extra",
+        "file:",
+        "file://remote/var/log/app.log",
+        "udp:host",
+        "udp://",
+        "udp://host:",
+        "tcp6://[::1",
+        "tcp://host/path",
+        "tcp://host?framing=unknown",
+        "tcp://host?port=601",
+        "udp://host?framing=octet-counting",
+    ];
+
+    for spec in invalid {
+        let err = spec.parse::<TargetSpec>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidTargetSpec, "{}", spec);
+    }
+}
+
+#[test]
+fn specs_display_as_uris() {
+    for spec in [
+        "stdout:",
+        "file:///var/log/app.log",
+        "file:app.log",
+        "unix:///dev/log",
+        "journald://",
+        "tcp6://[::1]:601",
+        "udp4://host",
+        "tcp4://host:601?framing=non-transparent",
+    ] {
+        assert_eq!(parse(spec).to_string(), spec);
+    }
+}
+
+#[test]
+fn file_spec_opens_file_target() {
+    let dir = log_dir();
+    let path = log_path(&dir, "spec");
+    let spec = TargetSpec::File(path.clone());
+
+    let target = spec.open().unwrap();
+    target.add_message("from a spec").unwrap();
+    drop(target);
+
+    assert!(fs::read_to_string(&path).unwrap().contains("from a spec"));
+}
+
+#[cfg(feature = "network")]
+#[test]
+fn network_spec_opens_network_target() {
+    let socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let spec = parse(&format!("udp://{}", socket.local_addr().unwrap()));
+
+    spec.open().unwrap().add_message("over udp").unwrap();
+
+    let mut buffer = [0u8; 1024];
+    let size = socket.recv(&mut buffer).unwrap();
+    assert!(String::from_utf8_lossy(&buffer[..size]).ends_with("over udp"));
+}
+
+#[cfg(not(feature = "journald"))]
+#[test]
+fn disabled_targets_are_not_opened() {
+    let err = parse("journald://").open().err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::TargetUnsupported);
+}
@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checks for the `Send` and `Sync` implementations of entries.
+//!
+//! Miri cannot run these as they call into Stumpless, so they are meant to be
+//! run under ThreadSanitizer instead:
+//!
+//! ```sh
+//! RUSTFLAGS=-Zsanitizer=thread CFLAGS=-fsanitize=thread \
+//!   cargo +nightly test -Zbuild-std --target x86_64-unknown-linux-gnu \
+//!   --test thread_safety
+//! ```
+
+use std::fs;
+use std::sync::mpsc::channel;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use stumpless::{Element, Entry, EntryTemplate, FileTarget, Param, Target};
+
+mod common;
+use common::{log_dir, log_path};
+
+const THREAD_COUNT: usize = 8;
+const ENTRIES_PER_THREAD: usize = 50;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn entry_types_are_send_and_sync() {
+    assert_send_sync::<Entry>();
+    assert_send_sync::<EntryTemplate>();
+    assert_send_sync::<Element>();
+    assert_send_sync::<Param>();
+}
+
+#[test]
+fn shared_entry_is_read_and_logged_from_threads() {
+    let dir = log_dir();
+    let path = log_path(&dir, "shared-entry");
+    let target = FileTarget::new(&path).unwrap();
+
+    let mut entry = Entry::builder()
+        .app_name("thread-test")
+        .message("shared entry")
+        .build()
+        .unwrap();
+    entry.add_new_param("shared@1", "key", "value").unwrap();
+    let entry = Arc::new(entry);
+
+    thread::scope(|scope| {
+        for _ in 0..THREAD_COUNT {
+            let entry = Arc::clone(&entry);
+            let target = &target;
+            scope.spawn(move || {
+                for _ in 0..ENTRIES_PER_THREAD {
+                    target.add_entry(&entry).unwrap();
+                    assert_eq!(entry.app_name().unwrap(), "thread-test");
+                    assert_eq!(entry.param_value("shared@1", "key").unwrap(), "value");
+                    assert!(entry
+                        .to_string()
+                        .ends_with("[shared@1 key=\"value\"] shared entry"));
+                }
+            });
+        }
+    });
+
+    drop(target);
+    let contents = fs::read_to_string(&path).unwrap();
+    assert_eq!(contents.lines().count(), THREAD_COUNT * ENTRIES_PER_THREAD);
+}
+
+#[test]
+fn locked_entry_is_changed_from_threads() {
+    let entry = Arc::new(Mutex::new(Entry::builder().build().unwrap()));
+
+    thread::scope(|scope| {
+        for i in 0..THREAD_COUNT {
+            let entry = Arc::clone(&entry);
+            scope.spawn(move || {
+                let element_name = format!("thread{}@1", i);
+                let mut entry = entry.lock().unwrap();
+                entry.add_new_element(&element_name).unwrap();
+                entry
+                    .add_new_param(&element_name, "index", &i.to_string())
+                    .unwrap();
+            });
+        }
+    });
+
+    let entry = entry.lock().unwrap();
+    assert_eq!(entry.element_count(), THREAD_COUNT);
+    for i in 0..THREAD_COUNT {
+        let element_name = format!("thread{}@1", i);
+        assert_eq!(
+            entry.param_value(&element_name, "index").unwrap(),
+            i.to_string()
+        );
+    }
+}
+
+#[test]
+fn entries_are_sent_between_threads() {
+    let template = Arc::new(EntryTemplate::new(Entry::builder().build().unwrap()));
+    let (sender, receiver) = channel();
+
+    thread::scope(|scope| {
+        for i in 0..THREAD_COUNT {
+            let template = Arc::clone(&template);
+            let sender = sender.clone();
+            scope.spawn(move || {
+                let mut element = Element::new("sent@1").unwrap();
+                element.add_new_param("thread", &i.to_string()).unwrap();
+
+                let mut entry = template.entry(&format!("from thread {}", i)).unwrap();
+                entry.add_element(&element).unwrap();
+                sender.send((i, entry)).unwrap();
+            });
+        }
+    });
+    drop(sender);
+
+    let mut received = 0;
+    for (i, mut entry) in receiver {
+        let param = entry.remove_param("sent@1", "thread").unwrap();
+        assert_eq!(param.value().unwrap(), i.to_string());
+        assert_eq!(
+            entry.message().unwrap().unwrap(),
+            format!("from thread {}", i)
+        );
+        received += 1;
+    }
+    assert_eq!(received, THREAD_COUNT);
+}
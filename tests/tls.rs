@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "tls")]
+
+use std::fs;
+use std::io::Read;
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use rcgen::{generate_simple_self_signed, CertifiedKey, KeyPair};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig, ServerConnection, StreamOwned};
+use stumpless::{ErrorKind, Target, TargetOption, TlsNetworkTarget};
+use tempfile::TempDir;
+
+// what a listener saw from the one client it accepted
+struct Received {
+    data: Vec<u8>,
+    closed_cleanly: bool,
+    server_name: Option<String>,
+    client_certs: usize,
+}
+
+fn certified_key(name: &str) -> CertifiedKey<KeyPair> {
+    generate_simple_self_signed(vec![name.to_string()]).unwrap()
+}
+
+fn pem_file(dir: &TempDir, filename: &str, contents: &str) -> String {
+    let path = dir.path().join(filename);
+    fs::write(&path, contents).unwrap();
+    path.to_str().unwrap().to_owned()
+}
+
+// accepts one connection and reads until the client closes it
+fn listen(
+    server: &CertifiedKey<KeyPair>,
+    client_ca: Option<&CertifiedKey<KeyPair>>,
+) -> (String, JoinHandle<Received>) {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let builder = ServerConfig::builder_with_provider(Arc::clone(&provider))
+        .with_safe_default_protocol_versions()
+        .unwrap();
+    let builder = match client_ca {
+        Some(client_ca) => {
+            let mut roots = RootCertStore::empty();
+            roots.add(client_ca.cert.der().clone()).unwrap();
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                .build()
+                .unwrap();
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let key = PrivatePkcs8KeyDer::from(server.signing_key.serialize_der());
+    let config = builder
+        .with_single_cert(
+            vec![CertificateDer::from(server.cert.der().to_vec())],
+            PrivateKeyDer::from(key),
+        )
+        .unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let handle = thread::spawn(move || {
+        let (socket, _) = listener.accept().unwrap();
+        let connection = ServerConnection::new(Arc::new(config)).unwrap();
+        let mut stream = StreamOwned::new(connection, socket);
+
+        let mut data = Vec::new();
+        let closed_cleanly = stream.read_to_end(&mut data).is_ok();
+        Received {
+            data,
+            closed_cleanly,
+            server_name: stream.conn.server_name().map(str::to_owned),
+            client_certs: stream
+                .conn
+                .peer_certificates()
+                .map_or(0, |certs| certs.len()),
+        }
+    });
+
+    (address, handle)
+}
+
+#[test]
+fn entries_are_octet_counted() {
+    let dir = tempfile::tempdir().unwrap();
+    let server = certified_key("localhost");
+    let ca_file = pem_file(&dir, "octet-ca.pem", &server.cert.pem());
+    let (address, listener) = listen(&server, None);
+
+    let target = TlsNetworkTarget::builder(&address)
+        .ca_file(&ca_file)
+        .server_name("localhost")
+        .build()
+        .unwrap();
+    target.add_message("first").unwrap();
+    target.add_message("line one\nline two").unwrap();
+    drop(target);
+
+    let received = listener.join().unwrap();
+    assert!(received.closed_cleanly);
+    assert_eq!(received.server_name.as_deref(), Some("localhost"));
+    assert_eq!(received.client_certs, 0);
+
+    let data = String::from_utf8(received.data).unwrap();
+    let (length, rest) = data.split_once(' ').unwrap();
+    let length: usize = length.parse().unwrap();
+    let (first, rest) = rest.split_at(length);
+    assert!(first.starts_with("<14>1 "));
+    assert!(first.ends_with(" first"));
+
+    let (length, rest) = rest.split_once(' ').unwrap();
+    assert_eq!(length.parse::<usize>().unwrap(), rest.len());
+    assert!(rest.ends_with(" line one\nline two"));
+}
+
+#[test]
+fn client_certificate_is_sent() {
+    let dir = tempfile::tempdir().unwrap();
+    let server = certified_key("localhost");
+    let client = certified_key("client.localhost");
+    let ca_file = pem_file(&dir, "client-ca.pem", &server.cert.pem());
+    let cert_file = pem_file(&dir, "client-cert.pem", &client.cert.pem());
+    let key_file = pem_file(&dir, "client-key.pem", &client.signing_key.serialize_pem());
+    let (address, listener) = listen(&server, Some(&client));
+
+    let target = TlsNetworkTarget::builder(&address)
+        .ca_file(&ca_file)
+        .client_cert(&cert_file, &key_file)
+        .server_name("localhost")
+        .build()
+        .unwrap();
+    target.add_message("authenticated").unwrap();
+    drop(target);
+
+    let received = listener.join().unwrap();
+    assert!(received.closed_cleanly);
+    assert_eq!(received.client_certs, 1);
+    assert!(String::from_utf8(received.data)
+        .unwrap()
+        .ends_with(" authenticated"));
+}
+
+#[test]
+fn untrusted_server_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let server = certified_key("localhost");
+    let other = certified_key("localhost");
+    let ca_file = pem_file(&dir, "untrusted-ca.pem", &other.cert.pem());
+    let (address, listener) = listen(&server, None);
+
+    let result = TlsNetworkTarget::builder(&address)
+        .ca_file(&ca_file)
+        .server_name("localhost")
+        .build();
+    assert!(result.is_err());
+
+    let received = listener.join().unwrap();
+    assert!(!received.closed_cleanly);
+    assert!(received.data.is_empty());
+}
+
+#[test]
+fn unreadable_certificates_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let empty_file = pem_file(&dir, "empty.pem", "");
+
+    for ca_file in [empty_file.as_str(), "/nonexistent/stumpless-ca.pem"] {
+        let err = TlsNetworkTarget::builder("localhost")
+            .ca_file(ca_file)
+            .build()
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), ErrorKind::TlsFailure, "{}", ca_file);
+    }
+}
+
+#[test]
+fn messages_use_target_options() {
+    let dir = tempfile::tempdir().unwrap();
+    let server = certified_key("localhost");
+    let ca_file = pem_file(&dir, "options-ca.pem", &server.cert.pem());
+    let (address, listener) = listen(&server, None);
+
+    let target = TlsNetworkTarget::builder(&address)
+        .ca_file(&ca_file)
+        .server_name("localhost")
+        .build()
+        .unwrap();
+    target.set_default_app_name("tls-app").unwrap();
+    target.set_option(TargetOption::Pid).unwrap();
+    target.add_message("with options").unwrap();
+    drop(target);
+
+    let data = String::from_utf8(listener.join().unwrap().data).unwrap();
+    let fields: Vec<&str> = data.splitn(9, ' ').collect();
+    assert_eq!(fields[4], "tls-app");
+    assert_eq!(fields[5], std::process::id().to_string());
+}
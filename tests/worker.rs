@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2023 Joel E. Anderson
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "tokio")]
+
+use std::sync::Arc;
+
+use stumpless::{
+    AsyncTarget, Entry, ErrorKind, FunctionTarget, Severity, StumplessError, WorkerTarget,
+};
+
+mod common;
+use common::recording_target;
+
+#[tokio::test]
+async fn requests_are_logged_in_order() {
+    let (target, messages) = recording_target("worker-order");
+    let worker = WorkerTarget::new(target, 4).unwrap();
+    let entry = Entry::builder()
+        .severity(Severity::Error)
+        .message("an entry")
+        .build()
+        .unwrap();
+
+    worker.add_entry(&entry).await.unwrap();
+    worker.add_message("a message").await.unwrap();
+    worker.log(Severity::Error, "a log").await.unwrap();
+    worker.flush().await.unwrap();
+    worker.close().await.unwrap();
+
+    assert_eq!(
+        *messages.lock().unwrap(),
+        ["an entry", "a message", "a log"]
+    );
+}
+
+#[tokio::test]
+async fn target_errors_are_returned() {
+    let target = FunctionTarget::new("worker-error", |_| {
+        Err(StumplessError::new(
+            ErrorKind::FunctionTargetFailure,
+            "rejected",
+        ))
+    })
+    .unwrap();
+    let worker = WorkerTarget::new(target, 1).unwrap();
+
+    let err = worker.add_message("rejected").await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::FunctionTargetFailure);
+}
+
+#[tokio::test]
+async fn concurrent_tasks_share_a_worker() {
+    let (target, messages) = recording_target("worker-tasks");
+    let worker = Arc::new(WorkerTarget::new(target, 2).unwrap());
+
+    let tasks: Vec<_> = (0..16)
+        .map(|i| {
+            let worker = Arc::clone(&worker);
+            tokio::spawn(async move { worker.add_message(&format!("task {}", i)).await })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+
+    assert_eq!(messages.lock().unwrap().len(), 16);
+}